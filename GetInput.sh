@@ -1,27 +1,13 @@
 #!/bin/bash
 
-if [ $1 -lt 1 -o $1 -gt 25 ]; then
-	echo "Must give day number"
+if [ "x$1" == "x" ]; then
+	echo "Usage: $0 <day> [<year>]"
 	exit 1
 fi
 
-day=$1
-daypad="$(printf %02d $day)"
-
-token="$(cat token.txt)"
-
-if [ "x$token" == "x" ]; then
-	echo "No token"
-	exit 2
-fi
-
-if [ ! -d inputs ]
-then
-	mkdir inputs
+if [ $1 -lt 1 -o $1 -gt 25 ]; then
+	echo "Must give day number"
+	exit 1
 fi
 
-curl -sS --cookie "session=$token" https://adventofcode.com/2025/day/$day/input -o inputs/day$daypad.txt
-
-if [ $? -ne 0 ]; then
-	echo "Failed to download"
-fi
+cargo run -q --release -p aoc --features fetch -- fetch ${2:+--year $2} $1
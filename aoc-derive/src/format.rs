@@ -0,0 +1,61 @@
+/// Part of a format string
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Segment {
+    /// Literal text which must match exactly
+    Literal(String),
+    /// Field placeholder, by name or tuple index
+    Field(String),
+}
+
+/// Splits a format string in to literals and `{field}` placeholders
+///
+/// Braces are escaped by doubling them
+pub(crate) fn parse_format(format: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                // Read the placeholder name
+                let mut name = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) if c.is_alphanumeric() || c == '_' => name.push(c),
+                        Some(c) => return Err(format!("Invalid character '{c}' in placeholder")),
+                        None => return Err("Unclosed placeholder".to_string()),
+                    }
+                }
+
+                if name.is_empty() {
+                    return Err("Placeholders must name a field".to_string());
+                }
+
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+
+                segments.push(Segment::Field(name));
+            }
+            '}' => return Err("Unmatched '}' (use '}}' for a literal brace)".to_string()),
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+
+    Ok(segments)
+}
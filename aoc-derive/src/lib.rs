@@ -0,0 +1,229 @@
+//! Derive macro for parsing input records from a format string
+//!
+//! Re-exported by the `aoc` crate as `aoc::parse::AocParse` with the `derive` feature.
+
+use std::collections::HashMap;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, LitStr};
+
+mod format;
+use format::{parse_format, Segment};
+
+/// Derives `aoc::parse::Parse` and `FromStr` for a type from a format string
+///
+/// Structs take a `#[aoc(format = "...")]` attribute naming each field in braces, with
+/// tuple fields named by index. Literal text between the fields must match exactly.
+/// Enums take a format on each variant, and the first variant to match is used.
+///
+/// Field types must implement `aoc::parse::Parse`, as integers and other derived types
+/// do. Fields are parsed from the start of the remaining input, so they may be adjacent.
+///
+/// ```ignore
+/// #[derive(AocParse)]
+/// #[aoc(format = "{x},{y}")]
+/// struct Coord {
+///     x: i64,
+///     y: i64,
+/// }
+///
+/// #[derive(AocParse)]
+/// enum Rotation {
+///     #[aoc(format = "L{0}")]
+///     Left(u16),
+///     #[aoc(format = "R{0}")]
+///     Right(u16),
+/// }
+///
+/// let coords = try_parse_input_vec(9, str::parse::<Coord>)?;
+/// ```
+#[proc_macro_derive(AocParse, attributes(aoc))]
+pub fn derive_aoc_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generates the `Parse` and `FromStr` implementations
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let format = format_attr(&input.attrs, name)?;
+
+            constructor(quote!(Self), &data.fields, &format)?
+        }
+        Data::Enum(data) => {
+            let variants = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let format = format_attr(&variant.attrs, variant)?;
+                    let ident = &variant.ident;
+
+                    let parse = constructor(quote!(Self::#ident), &variant.fields, &format)?;
+
+                    // Try the next variant unless this one failed part way through
+                    Ok(quote! {
+                        let input = start;
+
+                        match #parse {
+                            ::core::result::Result::Ok(result) => {
+                                return ::core::result::Result::Ok(result);
+                            }
+                            ::core::result::Result::Err(e) if ::aoc::parse::consumed(&e, start) => {
+                                return ::core::result::Result::Err(e);
+                            }
+                            ::core::result::Result::Err(_) => (),
+                        }
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            let name = name.to_string();
+
+            quote! {
+                let start = input;
+
+                #(#variants)*
+
+                ::core::result::Result::Err(::aoc::parse::ParseError::at(
+                    start,
+                    ::aoc::parse::Expected::Variant(#name),
+                ))
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "AocParse can't be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::aoc::parse::Parse for #name #ty_generics #where_clause {
+            fn parse_from(input: &str) -> ::aoc::parse::PResult<'_, Self> {
+                #body
+            }
+        }
+
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::aoc::input::TokenError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                ::aoc::parse::parse_all(<Self as ::aoc::parse::Parse>::parse_from, s)
+            }
+        }
+    })
+}
+
+/// Returns the format string from an `#[aoc(format = "...")]` attribute
+fn format_attr(attrs: &[Attribute], item: impl ToTokens) -> syn::Result<LitStr> {
+    let mut format = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("aoc")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("format") {
+                format = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("Unknown aoc attribute"))
+            }
+        })?;
+    }
+
+    format
+        .ok_or_else(|| syn::Error::new_spanned(item, "Missing #[aoc(format = \"...\")] attribute"))
+}
+
+/// Generates a block parsing the fields of a struct or variant in format order
+///
+/// The block evaluates to the parse result, breaking out early on an error.
+fn constructor(path: TokenStream2, fields: &Fields, format: &LitStr) -> syn::Result<TokenStream2> {
+    let error = |message: String| syn::Error::new(format.span(), message);
+
+    let segments = parse_format(&format.value()).map_err(error)?;
+
+    // Field names in declaration order, with the variables they are parsed in to
+    let names = match fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|field| field.ident.as_ref().unwrap().to_string())
+            .collect(),
+        Fields::Unnamed(fields) => (0..fields.unnamed.len()).map(|i| i.to_string()).collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    let vars = names
+        .iter()
+        .map(|name| format_ident!("__aoc_{}", name))
+        .collect::<Vec<_>>();
+
+    let mut types = names
+        .iter()
+        .zip(fields.iter())
+        .map(|(name, field)| (name.as_str(), Some(&field.ty)))
+        .collect::<HashMap<_, _>>();
+
+    // Parse each segment
+    let mut steps = Vec::new();
+
+    for segment in &segments {
+        match segment {
+            Segment::Literal(literal) => steps.push(quote! {
+                let input = match ::aoc::parse::tag(#literal)(input) {
+                    ::core::result::Result::Ok((_, rest)) => rest,
+                    ::core::result::Result::Err(e) => break 'parse ::core::result::Result::Err(e),
+                };
+            }),
+            Segment::Field(name) => {
+                let ty = match types.get_mut(name.as_str()) {
+                    Some(ty) => ty
+                        .take()
+                        .ok_or_else(|| error(format!("Field {name} appears more than once")))?,
+                    None => return Err(error(format!("No field named {name}"))),
+                };
+
+                let var = format_ident!("__aoc_{}", name);
+
+                steps.push(quote! {
+                    let (#var, input) = match <#ty as ::aoc::parse::Parse>::parse_from(input) {
+                        ::core::result::Result::Ok(result) => result,
+                        ::core::result::Result::Err(e) => break 'parse ::core::result::Result::Err(e),
+                    };
+                });
+            }
+        }
+    }
+
+    if let Some(name) = names.iter().find(|name| types[name.as_str()].is_some()) {
+        return Err(error(format!("Field {name} is missing from the format")));
+    }
+
+    // Build the value
+    let value = match fields {
+        Fields::Named(fields) => {
+            let idents = fields.named.iter().map(|field| &field.ident);
+
+            quote!(#path { #(#idents: #vars),* })
+        }
+        Fields::Unnamed(_) => quote!(#path(#(#vars),*)),
+        Fields::Unit => path,
+    };
+
+    Ok(quote! {
+        'parse: {
+            #(#steps)*
+
+            ::core::result::Result::Ok((#value, input))
+        }
+    })
+}
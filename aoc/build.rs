@@ -0,0 +1,88 @@
+use std::{
+    env,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+include!("src/puzzle/year.rs");
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/puzzle/year.rs");
+
+    // Only build the embedded input table when the feature is enabled
+    if env::var_os("CARGO_FEATURE_EMBED_INPUT").is_none() {
+        return;
+    }
+
+    println!("cargo:rerun-if-env-changed=AOC_INPUT_DIR");
+    println!("cargo:rerun-if-env-changed=AOC_YEAR");
+
+    // Embed inputs for the default year, as Puzzle::default_year does
+    let year = env::var("AOC_YEAR")
+        .ok()
+        .and_then(|year| year.trim().parse().ok())
+        .unwrap_or(DEFAULT_YEAR);
+
+    let dir = match env::var_os("AOC_INPUT_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(&env::var_os("CARGO_MANIFEST_DIR").unwrap()).join("../inputs"),
+    };
+
+    // Use the year directory if it exists or the flat layout otherwise, as year_dir does
+    let year_dir = dir.join(year.to_string());
+
+    println!("cargo:rerun-if-changed={}", dir.display());
+    println!("cargo:rerun-if-changed={}", year_dir.display());
+
+    let input_dir = if year_dir.is_dir() { &year_dir } else { &dir };
+
+    // Build a table entry for each day, embedding the input file if the day's
+    // embed-dayNN feature is enabled and the file is present
+    let mut entries = String::new();
+    let mut found = false;
+
+    for day in 1..=25 {
+        let file = format!("day{day:02}.txt");
+
+        let path = input_dir.join(&file);
+
+        let enabled = env::var_os(format!("CARGO_FEATURE_EMBED_DAY{day:02}")).is_some();
+
+        if enabled && path.is_file() {
+            let path = fs::canonicalize(&path).unwrap();
+
+            println!("cargo:rerun-if-changed={}", path.display());
+
+            writeln!(
+                entries,
+                "    Some(include_bytes!({:?})),",
+                path.display().to_string()
+            )
+            .unwrap();
+
+            found = true;
+        } else {
+            writeln!(entries, "    None,").unwrap();
+        }
+    }
+
+    if !found {
+        println!(
+            "cargo:warning=No input files for the enabled embed-dayNN features found in {} to embed",
+            input_dir.display()
+        );
+    }
+
+    let code = format!(
+        "/// Year the inputs were embedded for\n\
+         pub(crate) const YEAR: usize = {year};\n\n\
+         /// Inputs embedded at build time, indexed by day - 1\n\
+         pub(crate) static EMBEDDED: [Option<&[u8]>; 25] = [\n{entries}];\n"
+    );
+
+    let out = Path::new(&env::var_os("OUT_DIR").unwrap()).join("embedded.rs");
+
+    fs::write(out, code).unwrap();
+}
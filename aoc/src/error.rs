@@ -0,0 +1,135 @@
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use crate::input::{diagnostic, BoxError};
+
+/// Errors returned by the aoc library
+pub enum Error {
+    /// The input file could not be found at any of the paths tried
+    InputNotFound { paths: Vec<PathBuf> },
+    /// The input file exists but could not be opened or read
+    Io { path: PathBuf, source: io::Error },
+    /// The input file could not be memory mapped
+    Mmap { path: PathBuf, source: io::Error },
+    /// The input is not valid UTF-8 from the given byte offset
+    InvalidUtf8 { offset: usize },
+    /// The input contains no data
+    EmptyInput,
+    /// A transform failed on the given (1-based) line and optional (1-based) column
+    ///
+    /// `width` is the number of characters in the offending token
+    Transform {
+        file: Option<PathBuf>,
+        line: usize,
+        column: Option<usize>,
+        width: usize,
+        text: String,
+        source: BoxError,
+    },
+    /// A whole input transform failed at an unknown position
+    InputTransform { source: BoxError },
+    /// A grid line has a different width to the first line
+    GridWidth {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The input has a different number of sections to the number of section transforms
+    SectionCount { expected: usize, found: usize },
+    /// The input manifest is invalid on the given (1-based) line
+    Manifest {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+    /// A day's binary failed to build or run
+    Run { day: usize, message: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InputNotFound { paths } => {
+                write!(f, "Input file not found (tried ")?;
+
+                for (i, path) in paths.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    write!(f, "{}", path.display())?;
+                }
+
+                write!(f, ")")
+            }
+            Error::Io { path, source } => write!(f, "Error reading {}: {source}", path.display()),
+            Error::Mmap { path, source } => {
+                write!(f, "Error memory mapping {}: {source}", path.display())
+            }
+            Error::InvalidUtf8 { offset } => {
+                write!(f, "Input is not valid UTF-8 at byte offset {offset}")
+            }
+            Error::EmptyInput => write!(f, "Input is empty"),
+            Error::Transform {
+                file,
+                line,
+                column,
+                width,
+                text,
+                source,
+            } => diagnostic::render(f, file.as_deref(), *line, *column, *width, text, source),
+            Error::InputTransform { source } => write!(f, "Error transforming input: {source}"),
+            Error::GridWidth {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Line {line}: expected grid width {expected}, found {found}"
+            ),
+            Error::SectionCount { expected, found } => {
+                write!(f, "Expected {expected} sections in input, found {found}")
+            }
+            Error::Manifest {
+                path,
+                line,
+                message,
+            } => write!(f, "{}, line {line}: {message}", path.display()),
+            Error::Run { day, message } => write!(f, "Day {day} failed: {message}"),
+        }
+    }
+}
+
+// Errors returned from main are printed with Debug, so render them the same as Display
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Error {
+    /// Sets the input file name of a transform error
+    pub(crate) fn with_file(mut self, path: Option<&Path>) -> Self {
+        if let Error::Transform { file, .. } = &mut self {
+            if file.is_none() {
+                *file = path.map(Path::to_path_buf);
+            }
+        }
+
+        self
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } | Error::Mmap { source, .. } => Some(source),
+            Error::Transform { source, .. } | Error::InputTransform { source } => {
+                Some(source.as_ref())
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use std::{
+    fmt, fs,
+    io::{self, ErrorKind, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use ureq::Agent;
+
+use crate::Puzzle;
+
+/// Default base URL inputs are downloaded from
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable overriding the base URL inputs are downloaded from
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// Environment variable holding the session token, used in preference to token.txt
+pub const TOKEN_VAR: &str = "AOC_SESSION";
+
+/// Default minimum interval between requests
+const DEFAULT_THROTTLE: Duration = Duration::from_secs(5);
+
+/// File in the input directory recording the time of the last request
+const THROTTLE_FILE: &str = ".last-fetch";
+
+/// Text returned in place of an input when the session token is missing or invalid
+const NOT_LOGGED_IN: &str = "Puzzle inputs differ by user";
+
+/// User agent sent with requests, as requested by the site for automated tools
+const USER_AGENT: &str = "github.com/andywarduk/aoc2025 by andy.ward.uk@gmail.com";
+
+/// Errors returned by the input downloader
+#[derive(Debug)]
+pub enum FetchError {
+    /// The session token is not a hex string
+    InvalidToken,
+    /// The session token was not set in the environment or token file
+    TokenNotFound { path: PathBuf },
+    /// The day is out of range
+    InvalidDay { day: usize },
+    /// The input has already been downloaded
+    Cached { path: PathBuf },
+    /// The server rejected the session token
+    NotLoggedIn,
+    /// The puzzle does not exist or hasn't unlocked yet
+    NotAvailable { day: usize },
+    /// The server returned an unexpected status
+    Status { url: String, status: u16 },
+    /// The server returned a web page instead of an input
+    Html { url: String },
+    /// The server returned an empty input
+    Empty { url: String },
+    /// The request failed
+    Request { url: String, source: ureq::Error },
+    /// A file could not be read or written
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::InvalidToken => write!(f, "Session token is not a hex string"),
+            FetchError::TokenNotFound { path } => write!(
+                f,
+                "No session token found (set {TOKEN_VAR} or create {})",
+                path.display()
+            ),
+            FetchError::InvalidDay { day } => write!(f, "Day {day} is invalid"),
+            FetchError::Cached { path } => {
+                write!(f, "Input already downloaded to {}", path.display())
+            }
+            FetchError::NotLoggedIn => {
+                write!(f, "Session token was rejected (has it expired?)")
+            }
+            FetchError::NotAvailable { day } => {
+                write!(f, "Input for day {day} is not available yet")
+            }
+            FetchError::Status { url, status } => write!(f, "{url} returned status {status}"),
+            FetchError::Html { url } => write!(f, "{url} returned a web page, not an input"),
+            FetchError::Empty { url } => write!(f, "{url} returned an empty input"),
+            FetchError::Request { url, source } => write!(f, "Error requesting {url}: {source}"),
+            FetchError::Io { path, source } => {
+                write!(f, "Error accessing {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for FetchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FetchError::Request { source, .. } => Some(source),
+            FetchError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the session token from the environment or a token file
+pub fn load_token(path: &Path) -> Result<String, FetchError> {
+    if let Ok(token) = std::env::var(TOKEN_VAR) {
+        return Ok(token);
+    }
+
+    match fs::read_to_string(path) {
+        Ok(token) => Ok(token),
+        Err(e) if e.kind() == ErrorKind::NotFound => Err(FetchError::TokenNotFound {
+            path: path.to_path_buf(),
+        }),
+        Err(source) => Err(FetchError::Io {
+            path: path.to_path_buf(),
+            source,
+        }),
+    }
+}
+
+/// Downloads puzzle inputs in to an input directory
+pub struct Fetcher {
+    agent: Agent,
+    base_url: String,
+    token: String,
+    throttle: Duration,
+}
+
+impl Fetcher {
+    /// Creates a new fetcher for a session token
+    pub fn new(token: &str) -> Result<Self, FetchError> {
+        let token = token.trim();
+        let token = token.strip_prefix("session=").unwrap_or(token);
+
+        if token.len() < 32 || !token.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(FetchError::InvalidToken);
+        }
+
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+
+        Ok(Self {
+            agent,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            token: token.to_string(),
+            throttle: DEFAULT_THROTTLE,
+        })
+    }
+
+    /// Sets the base URL to download from
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets the minimum interval between requests
+    pub fn throttle(mut self, throttle: Duration) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// Downloads the input for a puzzle or day in to an input directory, returning the
+    /// path written
+    ///
+    /// The input is written to the puzzle's year directory, or the input directory itself
+    /// for the default year if that doesn't exist. Fails without making a request if the
+    /// input has already been downloaded.
+    pub fn fetch(&self, puzzle: impl Into<Puzzle>, base: &Path) -> Result<PathBuf, FetchError> {
+        let puzzle = puzzle.into();
+        let Puzzle { year, day } = puzzle;
+
+        if !(1..=25).contains(&day) {
+            return Err(FetchError::InvalidDay { day });
+        }
+
+        let dir = &puzzle.input_dir(base);
+        let path = dir.join(puzzle.file_name());
+
+        if path.exists() {
+            return Err(FetchError::Cached { path });
+        }
+
+        fs::create_dir_all(dir).map_err(|source| FetchError::Io {
+            path: dir.to_path_buf(),
+            source,
+        })?;
+
+        // Download the input
+        self.wait(base);
+
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+
+        let body = self.get(&url);
+
+        self.record(base);
+
+        let body = check_response(day, &url, body?)?;
+
+        // Write the input, failing if it has appeared in the meantime
+        let mut file = match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                return Err(FetchError::Cached { path })
+            }
+            Err(source) => return Err(FetchError::Io { path, source }),
+        };
+
+        file.write_all(body.as_bytes())
+            .map_err(|source| FetchError::Io {
+                path: path.clone(),
+                source,
+            })?;
+
+        Ok(path)
+    }
+
+    /// Requests a URL, returning the status and body
+    fn get(&self, url: &str) -> Result<(u16, String), FetchError> {
+        let request_error = |source| FetchError::Request {
+            url: url.to_string(),
+            source,
+        };
+
+        let mut response = self
+            .agent
+            .get(url)
+            .header("Cookie", &format!("session={}", self.token))
+            .call()
+            .map_err(request_error)?;
+
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .map_err(request_error)?;
+
+        Ok((response.status().as_u16(), body))
+    }
+
+    /// Sleeps until the throttle interval has passed since the last recorded request
+    fn wait(&self, dir: &Path) {
+        let Ok(last) = fs::read_to_string(dir.join(THROTTLE_FILE)) else {
+            return;
+        };
+
+        let Ok(last) = last.trim().parse::<u64>() else {
+            return;
+        };
+
+        let next = Duration::from_millis(last) + self.throttle;
+
+        if let Some(delay) = next.checked_sub(now()) {
+            thread::sleep(delay);
+        }
+    }
+
+    /// Records the time of a request
+    fn record(&self, dir: &Path) {
+        // Failing to record the time only loses throttling for the next run
+        let _ = fs::write(dir.join(THROTTLE_FILE), now().as_millis().to_string());
+    }
+}
+
+/// Returns the time since the unix epoch
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Checks a response is a puzzle input, returning the body
+fn check_response(
+    day: usize,
+    url: &str,
+    (status, body): (u16, String),
+) -> Result<String, FetchError> {
+    if body.contains(NOT_LOGGED_IN) {
+        return Err(FetchError::NotLoggedIn);
+    }
+
+    match status {
+        200 => (),
+        404 => return Err(FetchError::NotAvailable { day }),
+        _ => {
+            return Err(FetchError::Status {
+                url: url.to_string(),
+                status,
+            })
+        }
+    }
+
+    let start = body.trim_start();
+
+    if start.starts_with('<') {
+        return Err(FetchError::Html {
+            url: url.to_string(),
+        });
+    }
+
+    if start.is_empty() {
+        return Err(FetchError::Empty {
+            url: url.to_string(),
+        });
+    }
+
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,178 @@
+use std::{
+    io::{BufRead, BufReader},
+    net::TcpListener,
+    thread::JoinHandle,
+    time::Instant,
+};
+
+use super::*;
+use crate::test_util::TempDir;
+
+const TOKEN: &str = "0123456789abcdef0123456789abcdef";
+
+/// Starts a local stand-in server returning canned responses, one per connection
+///
+/// Returns the base URL and a handle which yields the request heads received
+fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = thread::spawn(move || {
+        let mut requests = Vec::new();
+
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            // Read the request head
+            let mut head = String::new();
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+
+                head.push_str(&line);
+            }
+
+            requests.push(head);
+
+            write!(
+                stream,
+                "HTTP/1.1 {status} Stand-in\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        }
+
+        requests
+    });
+
+    (base_url, handle)
+}
+
+fn fetcher(base_url: &str) -> Fetcher {
+    Fetcher::new(TOKEN)
+        .unwrap()
+        .base_url(base_url)
+        .throttle(Duration::ZERO)
+}
+
+#[test]
+fn fetch_ok() {
+    let (base_url, server) = serve(vec![(200, "1\n2\n3\n")]);
+    let temp = TempDir::new("fetch-ok");
+    let dir = temp.path();
+
+    let path = fetcher(&base_url).fetch(3, dir).unwrap();
+
+    assert_eq!(path, dir.join("day03.txt"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n3\n");
+
+    let requests = server.join().unwrap();
+
+    assert!(requests[0].starts_with("GET /2025/day/3/input "));
+    assert!(requests[0].contains(&format!("session={TOKEN}")));
+}
+
+#[test]
+fn fetch_year() {
+    let (base_url, server) = serve(vec![(200, "1\n")]);
+    let temp = TempDir::new("fetch-year");
+    let dir = temp.path();
+
+    let path = fetcher(&base_url).fetch(Puzzle::new(2017, 5), dir).unwrap();
+
+    assert_eq!(path, dir.join("2017").join("day05.txt"));
+
+    let requests = server.join().unwrap();
+
+    assert!(requests[0].starts_with("GET /2017/day/5/input "));
+}
+
+#[test]
+fn fetch_cached() {
+    let temp = TempDir::new("fetch-cached");
+    let dir = temp.path();
+
+    fs::write(dir.join("day01.txt"), "cached").unwrap();
+
+    // No server, so any request would fail
+    let result = fetcher("http://127.0.0.1:1").fetch(1, dir);
+
+    assert!(matches!(result, Err(FetchError::Cached { .. })));
+    assert_eq!(fs::read_to_string(dir.join("day01.txt")).unwrap(), "cached");
+}
+
+#[test]
+fn fetch_not_logged_in() {
+    let (base_url, server) = serve(vec![(
+        400,
+        "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+    )]);
+    let temp = TempDir::new("fetch-login");
+    let dir = temp.path();
+
+    let result = fetcher(&base_url).fetch(2, dir);
+
+    assert!(matches!(result, Err(FetchError::NotLoggedIn)));
+    assert!(!dir.join("day02.txt").exists());
+
+    server.join().unwrap();
+}
+
+#[test]
+fn fetch_not_available() {
+    let (base_url, server) = serve(vec![
+        (
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        ),
+        (200, "<!DOCTYPE html>\n<html lang=\"en-us\">\n</html>\n"),
+    ]);
+    let temp = TempDir::new("fetch-404");
+    let dir = temp.path();
+
+    let fetcher = fetcher(&base_url);
+
+    let result = fetcher.fetch(25, dir);
+    assert!(matches!(result, Err(FetchError::NotAvailable { day: 25 })));
+
+    let result = fetcher.fetch(25, dir);
+    assert!(matches!(result, Err(FetchError::Html { .. })));
+
+    assert!(!dir.join("day25.txt").exists());
+
+    server.join().unwrap();
+}
+
+#[test]
+fn fetch_throttled() {
+    let (base_url, server) = serve(vec![(200, "1\n"), (200, "2\n")]);
+    let temp = TempDir::new("fetch-throttle");
+    let dir = temp.path();
+
+    let fetcher = fetcher(&base_url).throttle(Duration::from_millis(300));
+
+    let start = Instant::now();
+
+    fetcher.fetch(1, dir).unwrap();
+    fetcher.fetch(2, dir).unwrap();
+
+    assert!(start.elapsed() >= Duration::from_millis(300));
+
+    server.join().unwrap();
+}
+
+#[test]
+fn invalid_token() {
+    assert!(matches!(Fetcher::new(""), Err(FetchError::InvalidToken)));
+    assert!(matches!(
+        Fetcher::new("not a token"),
+        Err(FetchError::InvalidToken)
+    ));
+    assert!(Fetcher::new(&format!("session={TOKEN}\n")).is_ok());
+}
@@ -0,0 +1,277 @@
+/// Paletted image with flat storage and drawing primitives
+///
+/// Positions are signed and drawing is clipped to the canvas, so shapes and sprites
+/// may extend past its edges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    width: u16,
+    height: u16,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    /// Creates a new canvas filled with palette index 0
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize],
+        }
+    }
+
+    /// Returns the width of the canvas
+    #[inline]
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Returns the height of the canvas
+    #[inline]
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Returns the pixels of the canvas in row order
+    #[inline]
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Returns a row of the canvas
+    #[inline]
+    pub fn row(&self, y: u16) -> &[u8] {
+        let start = y as usize * self.width as usize;
+
+        &self.pixels[start..start + self.width as usize]
+    }
+
+    /// Returns an iterator over the rows of the canvas
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> {
+        self.pixels.chunks_exact(self.width.max(1) as usize)
+    }
+
+    /// Returns the pixel at a position, or None if it is outside the canvas
+    pub fn get(&self, x: i32, y: i32) -> Option<u8> {
+        self.offset(x, y).map(|offset| self.pixels[offset])
+    }
+
+    /// Sets the pixel at a position, ignoring positions outside the canvas
+    pub fn set(&mut self, x: i32, y: i32, colour: u8) {
+        if let Some(offset) = self.offset(x, y) {
+            self.pixels[offset] = colour;
+        }
+    }
+
+    /// Fills the whole canvas
+    pub fn fill(&mut self, colour: u8) {
+        self.pixels.fill(colour);
+    }
+
+    /// Fills a rectangle
+    pub fn rect(&mut self, x: i32, y: i32, width: u16, height: u16, colour: u8) {
+        // Clip to the canvas, working in i64 so the far edges can't overflow
+        let (x, y) = (x as i64, y as i64);
+        let x0 = x.clamp(0, self.width as i64) as usize;
+        let x1 = (x + width as i64).clamp(0, self.width as i64) as usize;
+
+        if x0 == x1 {
+            return;
+        }
+
+        for y in y.max(0)..(y + height as i64).min(self.height as i64) {
+            let start = y as usize * self.width as usize;
+
+            self.pixels[start + x0..start + x1].fill(colour);
+        }
+    }
+
+    /// Draws a line between two points inclusive
+    ///
+    /// Draws the same pixels as Bresenham's algorithm, but only steps over the part of
+    /// the line on the canvas.
+    pub fn line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, colour: u8) {
+        let (x0, y0, x1, y1) = (x0 as i64, y0 as i64, x1 as i64, y1 as i64);
+        let (dx, dy) = ((x1 - x0).abs(), (y1 - y0).abs());
+        let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+
+        // Step along the longer axis, rounding the other axis to the nearest pixel
+        let steps = dx.max(dy);
+
+        let point = |step: i64| {
+            if dx >= dy {
+                (x0 + sx * step, y0 + sy * scale(step, dy, dx))
+            } else {
+                (x0 + sx * scale(step, dx, dy), y0 + sy * step)
+            }
+        };
+
+        // Both coordinates move monotonically, so the steps on the canvas are a range
+        let (width, height) = (self.width as i64, self.height as i64);
+
+        let before = |step| {
+            let (x, y) = point(step);
+            before_range(x, sx, width) || before_range(y, sy, height)
+        };
+
+        let past = |step| {
+            let (x, y) = point(step);
+            past_range(x, sx, width) || past_range(y, sy, height)
+        };
+
+        let start = partition(0, steps + 1, |step| !before(step));
+        let end = partition(start, steps + 1, past);
+
+        for step in start..end {
+            let (x, y) = point(step);
+
+            self.set(x as i32, y as i32, colour);
+        }
+    }
+
+    /// Fills a circle with a given centre and radius
+    pub fn circle(&mut self, cx: i32, cy: i32, radius: u16, colour: u8) {
+        // Work in i64 so large radii can't overflow
+        let (cx, cy, r) = (cx as i64, cy as i64, radius as i64);
+        let width = self.width as i64;
+
+        // Only visit rows on the canvas
+        for y in (cy - r).max(0)..=(cy + r).min(self.height as i64 - 1) {
+            let dy = y - cy;
+
+            // Measuring to pixel edges rather than centres gives a rounder shape
+            let dx = (r * r + r - dy * dy).isqrt();
+
+            // Clip the span to the canvas
+            let x0 = (cx - dx).clamp(0, width) as usize;
+            let x1 = (cx + dx + 1).clamp(0, width) as usize;
+            let start = y as usize * self.width as usize;
+
+            self.pixels[start + x0..start + x1].fill(colour);
+        }
+    }
+
+    /// Copies a sprite on to the canvas with its top left corner at a position
+    ///
+    /// The sprite is a slice of rows of palette indexes. Pixels matching the
+    /// transparent index, if given, are left unchanged.
+    pub fn blit<R: AsRef<[u8]>>(&mut self, x: i32, y: i32, sprite: &[R], transparent: Option<u8>) {
+        self.blit_rows(x, y, sprite.iter().map(AsRef::as_ref), |pixel| {
+            (Some(pixel) != transparent).then_some(pixel)
+        });
+    }
+
+    /// Copies another canvas on to the canvas with its top left corner at a position
+    ///
+    /// Pixels matching the transparent index, if given, are left unchanged.
+    pub fn blit_canvas(&mut self, x: i32, y: i32, tile: &Canvas, transparent: Option<u8>) {
+        self.blit_rows(x, y, tile.rows(), |pixel| {
+            (Some(pixel) != transparent).then_some(pixel)
+        });
+    }
+
+    /// Draws a single colour through a mask with its top left corner at a position
+    ///
+    /// Pixels are drawn wherever the mask is non-zero.
+    pub fn stamp<R: AsRef<[u8]>>(&mut self, x: i32, y: i32, mask: &[R], colour: u8) {
+        self.blit_rows(x, y, mask.iter().map(AsRef::as_ref), |pixel| {
+            (pixel != 0).then_some(colour)
+        });
+    }
+
+    /// Copies rows of pixels on to the canvas, mapping each to a colour or None to skip it
+    fn blit_rows<'a>(
+        &mut self,
+        x: i32,
+        y: i32,
+        rows: impl Iterator<Item = &'a [u8]>,
+        map: impl Fn(u8) -> Option<u8>,
+    ) {
+        for (row, dy) in rows.zip(y..) {
+            if dy < 0 {
+                continue;
+            }
+
+            if dy >= self.height as i32 {
+                break;
+            }
+
+            for (&pixel, dx) in row.iter().zip(x..) {
+                if let Some(colour) = map(pixel) {
+                    self.set(dx, dy, colour);
+                }
+            }
+        }
+    }
+
+    /// Returns the offset of a position in the pixel buffer, or None if it is outside the canvas
+    #[inline]
+    fn offset(&self, x: i32, y: i32) -> Option<usize> {
+        if (0..self.width as i32).contains(&x) && (0..self.height as i32).contains(&y) {
+            Some(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+}
+
+impl From<Vec<Vec<u8>>> for Canvas {
+    /// Creates a canvas from rows of palette indexes, which must all be the same length
+    fn from(rows: Vec<Vec<u8>>) -> Self {
+        let width = rows.first().map_or(0, Vec::len);
+
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "Canvas rows must all be the same length"
+        );
+
+        Self {
+            width: u16::try_from(width).expect("Canvas rows must be at most 65535 pixels long"),
+            height: u16::try_from(rows.len()).expect("Canvas must have at most 65535 rows"),
+            pixels: rows.concat(),
+        }
+    }
+}
+
+/// Returns `step * num / den` rounded to the nearest integer, with halves rounded up
+fn scale(step: i64, num: i64, den: i64) -> i64 {
+    if den == 0 {
+        return 0;
+    }
+
+    ((2 * step as i128 * num as i128 + den as i128) / (2 * den as i128)) as i64
+}
+
+/// Returns true if a coordinate moving in a direction hasn't reached `0..limit` yet
+fn before_range(pos: i64, dir: i64, limit: i64) -> bool {
+    match dir {
+        1 => pos < 0,
+        -1 => pos >= limit,
+        // A fixed coordinate outside the range never reaches it
+        _ => !(0..limit).contains(&pos),
+    }
+}
+
+/// Returns true if a coordinate moving in a direction has passed `0..limit`
+fn past_range(pos: i64, dir: i64, limit: i64) -> bool {
+    match dir {
+        1 => pos >= limit,
+        -1 => pos < 0,
+        _ => false,
+    }
+}
+
+/// Returns the first value in `start..end` for which a monotonic predicate is true,
+/// or `end` if there is none
+fn partition(mut start: i64, mut end: i64, pred: impl Fn(i64) -> bool) -> i64 {
+    while start < end {
+        let mid = start + (end - start) / 2;
+
+        if pred(mid) {
+            end = mid;
+        } else {
+            start = mid + 1;
+        }
+    }
+
+    start
+}
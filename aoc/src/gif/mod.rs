@@ -1,10 +1,25 @@
-use std::{borrow::Cow, cmp::max, error::Error, fs::File};
+use std::{
+    error::Error,
+    fs::File,
+    sync::mpsc::{sync_channel, SyncSender},
+    thread::{self, JoinHandle},
+};
 
-use gif::{Encoder, Frame, Repeat};
+use gif::{Encoder, EncodingError, Repeat};
 
-use self::region::Region;
+use self::{region::Region, writer::Writer};
+
+mod canvas;
+pub use canvas::Canvas;
+
+mod palette;
+pub use palette::{Palette, PaletteBuilder, PaletteError, Ramp, MAX_COLOURS};
 
 mod region;
+mod writer;
+
+/// Maximum number of queued GIF frames the encoder thread compresses together
+const BATCH_FRAMES: usize = 64;
 
 pub struct Gif {
     width: u16,
@@ -13,15 +28,31 @@ pub struct Gif {
     y_scale: u16,
     gif_width: u16,
     gif_height: u16,
-    encoder: Encoder<File>,
-    last_frame: Option<Vec<Vec<u8>>>,
+    output: Option<Output>,
+}
+
+/// Destination for frames, encoded either on the caller's thread or a background thread
+enum Output {
+    Direct(Writer),
+    Pipelined {
+        sender: SyncSender<Command>,
+        handle: JoinHandle<Result<(), EncodingError>>,
+    },
+}
+
+/// Request sent to the encoder thread
+enum Command {
+    Frame(Canvas, u16, IdenticalAction),
+    Delay(u16),
 }
 
 impl Gif {
     /// Creates a new GIF with a given palette, size and scale
+    ///
+    /// The palette is a slice of colours or a [`Palette`].
     pub fn new(
         file: &str,
-        palette: &[[u8; 3]],
+        palette: impl AsRef<[[u8; 3]]>,
         width: u16,
         height: u16,
         x_scale: u16,
@@ -31,7 +62,12 @@ impl Gif {
         let gif_height = height * y_scale;
 
         // Create the flattened palette
-        let flat_pal = palette.iter().flatten().cloned().collect::<Vec<_>>();
+        let flat_pal = palette
+            .as_ref()
+            .iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>();
 
         // Create the encoder
         let mut encoder = Encoder::new(File::create(file)?, gif_width, gif_height, &flat_pal)?;
@@ -39,6 +75,8 @@ impl Gif {
         // Ininitely repeat
         encoder.set_repeat(Repeat::Infinite)?;
 
+        let writer = Writer::new(encoder, width, height, x_scale, y_scale);
+
         Ok(Self {
             width,
             height,
@@ -46,15 +84,64 @@ impl Gif {
             y_scale,
             gif_width,
             gif_height,
-            encoder,
-            last_frame: None,
+            output: Some(Output::Direct(writer)),
         })
     }
 
+    /// Writes a palette index for pixels unchanged since the last frame
+    ///
+    /// Unchanged pixels inside each changed region are written as transparent so
+    /// they compress better, so frames should not draw with the index. Must be
+    /// called before [`Gif::pipelined`].
+    pub fn transparent(mut self, index: u8) -> Self {
+        match &mut self.output {
+            Some(Output::Direct(writer)) => writer.set_transparent(index),
+            _ => panic!("Gif::transparent must be called before Gif::pipelined"),
+        }
+
+        self
+    }
+
+    /// Encodes frames on a background thread, queueing up to `depth` frames
+    ///
+    /// Drawing returns as soon as the frame is queued, so the caller can carry on
+    /// producing the next frame. Call [`Gif::finish`] to wait for the encoder and
+    /// collect any error. With the `parallel` feature, queued frames are compressed
+    /// in parallel.
+    pub fn pipelined(mut self, depth: usize) -> Self {
+        if let Some(Output::Direct(mut writer)) = self.output.take() {
+            let (sender, receiver) = sync_channel(depth);
+
+            let handle = thread::spawn(move || {
+                while let Ok(command) = receiver.recv() {
+                    let mut frames = writer.frames(command);
+
+                    // Batch up frames already queued so they can be compressed together
+                    while frames.len() < BATCH_FRAMES {
+                        match receiver.try_recv() {
+                            Ok(command) => frames.extend(writer.frames(command)),
+                            Err(_) => break,
+                        }
+                    }
+
+                    writer.write(frames)?;
+                }
+
+                writer.finish()
+            });
+
+            self.output = Some(Output::Pipelined { sender, handle });
+        }
+
+        self
+    }
+
     /// Output a frame to the GIF
+    ///
+    /// Accepts a `Canvas` or rows of palette indexes
     pub fn draw_frame(
         &mut self,
-        frame_data: Vec<Vec<u8>>,
+        frame_data: impl Into<Canvas>,
         delay: u16,
     ) -> Result<(), Box<dyn Error>> {
         self.draw_frame_identical_check(frame_data, delay, IdenticalAction::Ignore)
@@ -63,89 +150,30 @@ impl Gif {
     /// Output a frame to the GIF and takes a given action if the frame is identical to the last
     pub fn draw_frame_identical_check(
         &mut self,
-        frame_data: Vec<Vec<u8>>,
+        frame_data: impl Into<Canvas>,
         delay: u16,
         identical_action: IdenticalAction,
     ) -> Result<(), Box<dyn Error>> {
-        // Make sure the frame looks like the correct size
-        assert_eq!(frame_data.len(), self.height as usize);
-        assert_eq!(frame_data[0].len(), self.width as usize);
-
-        // Calculate the difference between this frame and the last
-        match self.frame_difference(&frame_data) {
-            None => {
-                // No difference
-                match identical_action {
-                    IdenticalAction::Delay => self.delay(delay)?,
-                    IdenticalAction::Ignore => (),
-                }
-            }
-            Some(difference) => {
-                // Scale the frame up
-                let out_section = frame_data
-                    .iter()
-                    .enumerate()
-                    .filter_map(|(y, l)| {
-                        if difference.contains_y(y as u16) {
-                            Some(&l[difference.x_range()])
-                        } else {
-                            None
-                        }
-                    })
-                    .fold(
-                        Vec::with_capacity(self.gif_height as usize * self.gif_width as usize),
-                        |mut acc: Vec<u8>, line| {
-                            let expanded_line: Vec<u8> = line
-                                .iter()
-                                .flat_map(|pix| vec![*pix; self.x_scale as usize])
-                                .collect();
-
-                            for _ in 0..self.y_scale {
-                                acc.extend(&expanded_line);
-                            }
-
-                            acc
-                        },
-                    );
-
-                // Create the next frame
-                let frame = Frame {
-                    top: difference.top() * self.y_scale,
-                    left: difference.left() * self.x_scale,
-                    width: difference.width() * self.x_scale,
-                    height: difference.height() * self.y_scale,
-                    buffer: Cow::Borrowed(&*out_section),
-                    delay: max(2, delay),
-                    ..Default::default()
-                };
-
-                // Write out the frame
-                self.encoder.write_frame(&frame)?;
-
-                // Save the last frame
-                self.last_frame = Some(frame_data);
-            }
-        }
+        let frame_data = frame_data.into();
 
-        Ok(())
+        // Make sure the frame is the correct size
+        assert_eq!(frame_data.height(), self.height);
+        assert_eq!(frame_data.width(), self.width);
+
+        self.send(Command::Frame(frame_data, delay, identical_action))
     }
 
     /// Creates an empty delay frame
     pub fn delay(&mut self, delay: u16) -> Result<(), Box<dyn Error>> {
-        // Create the next frame
-        let frame = Frame {
-            delay: max(2, delay),
-            width: 1,
-            height: 1,
-            transparent: Some(0),
-            buffer: Cow::Owned(vec![0]),
-            ..Default::default()
-        };
-
-        // Write out the frame
-        self.encoder.write_frame(&frame)?;
+        self.send(Command::Delay(delay))
+    }
 
-        Ok(())
+    /// Finishes writing the GIF, waiting for the encoder thread if pipelined
+    ///
+    /// Returns any error from encoding queued frames. The GIF is also finished
+    /// when dropped, but errors are then ignored.
+    pub fn finish(mut self) -> Result<(), Box<dyn Error>> {
+        self.close()
     }
 
     /// Returns dimensions of the pre-scaled image
@@ -159,41 +187,48 @@ impl Gif {
 
     /// Returns a new empty frame for the image
     #[inline]
-    pub fn empty_frame(&self) -> Vec<Vec<u8>> {
+    pub fn empty_frame(&self) -> Canvas {
         let (w, h) = self.dimensions();
 
-        vec![vec![0; w as usize]; h as usize]
+        Canvas::new(w, h)
     }
 
-    /// Calculates the difference between a given frame and the last frame output
-    /// Returns None if there is no difference
-    /// If there is no previous frame then the whole frame is considered changed
-    fn frame_difference(&self, frame_data: &[Vec<u8>]) -> Option<Region> {
-        if let Some(last_frame) = &self.last_frame {
-            let mut region = Region::max_init();
-
-            // Process each row
-            for (y, (l1, l2)) in last_frame.iter().zip(frame_data.iter()).enumerate() {
-                // Process each pixel
-                for (x, (_, _)) in l1
-                    .iter()
-                    .zip(l2.iter())
-                    .enumerate()
-                    .filter(|(_, (p1, p2))| *p1 != *p2)
-                {
-                    region.max_add(x as u16, y as u16)
+    /// Writes or queues a frame
+    fn send(&mut self, command: Command) -> Result<(), Box<dyn Error>> {
+        match &mut self.output {
+            Some(Output::Direct(writer)) => writer.run(command)?,
+            Some(Output::Pipelined { sender, .. }) => {
+                if sender.send(command).is_err() {
+                    // The encoder thread has stopped, so collect its error
+                    self.close()?;
+                    Err("GIF encoder thread stopped")?
                 }
             }
+            None => Err("GIF output is closed")?,
+        }
 
-            if region.max_valid() {
-                Some(region)
-            } else {
-                None
+        Ok(())
+    }
+
+    /// Finishes the output, joining the encoder thread if there is one
+    fn close(&mut self) -> Result<(), Box<dyn Error>> {
+        match self.output.take() {
+            Some(Output::Direct(writer)) => writer.finish()?,
+            Some(Output::Pipelined { sender, handle }) => {
+                drop(sender);
+
+                handle.join().map_err(|_| "GIF encoder thread panicked")??
             }
-        } else {
-            // No previous frame
-            Some(Region::new(0, 0, self.height - 1, self.width - 1))
+            None => (),
         }
+
+        Ok(())
+    }
+}
+
+impl Drop for Gif {
+    fn drop(&mut self) {
+        let _ = self.close();
     }
 }
 
@@ -202,3 +237,6 @@ pub enum IdenticalAction {
     Ignore,
     Delay,
 }
+
+#[cfg(test)]
+mod tests;
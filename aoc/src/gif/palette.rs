@@ -0,0 +1,244 @@
+use std::{collections::HashMap, error::Error, fmt, ops::RangeInclusive};
+
+/// Maximum number of colours in a GIF palette
+pub const MAX_COLOURS: usize = 256;
+
+/// Errors returned when building a palette
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteError {
+    /// The palette has more than 256 colours
+    TooManyColours { count: usize },
+    /// Two entries have the same name
+    DuplicateName { name: String },
+    /// An entry has no colours
+    Empty { name: String },
+    /// More than one transparent index was reserved
+    MultipleTransparent,
+}
+
+impl fmt::Display for PaletteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaletteError::TooManyColours { count } => {
+                write!(f, "Palette has {count} colours (maximum {MAX_COLOURS})")
+            }
+            PaletteError::DuplicateName { name } => {
+                write!(f, "Palette entry {name} is defined more than once")
+            }
+            PaletteError::Empty { name } => write!(f, "Palette entry {name} has no colours"),
+            PaletteError::MultipleTransparent => {
+                write!(f, "Palette has more than one transparent index")
+            }
+        }
+    }
+}
+
+impl Error for PaletteError {}
+
+/// Perceptually uniform colour ramps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ramp {
+    /// Dark blue through green to yellow
+    Viridis,
+    /// Black through purple and orange to pale yellow
+    Magma,
+}
+
+impl Ramp {
+    /// Returns evenly spaced colours along the ramp to interpolate between
+    fn stops(self) -> &'static [[u8; 3]] {
+        match self {
+            Ramp::Viridis => &[
+                [0x44, 0x01, 0x54],
+                [0x47, 0x2d, 0x7b],
+                [0x3b, 0x52, 0x8b],
+                [0x2c, 0x72, 0x8e],
+                [0x21, 0x91, 0x8c],
+                [0x28, 0xae, 0x80],
+                [0x5e, 0xc9, 0x62],
+                [0xad, 0xdc, 0x30],
+                [0xfd, 0xe7, 0x25],
+            ],
+            Ramp::Magma => &[
+                [0x00, 0x00, 0x04],
+                [0x1c, 0x10, 0x44],
+                [0x4f, 0x12, 0x7b],
+                [0x81, 0x25, 0x81],
+                [0xb5, 0x36, 0x7a],
+                [0xe5, 0x50, 0x64],
+                [0xfb, 0x87, 0x61],
+                [0xfe, 0xc2, 0x87],
+                [0xfc, 0xfd, 0xbf],
+            ],
+        }
+    }
+}
+
+/// GIF colour palette with named entries
+///
+/// Each entry is a single colour or a run of gradient colours, and is looked up by
+/// name to get its palette indexes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    colours: Vec<[u8; 3]>,
+    names: HashMap<String, RangeInclusive<u8>>,
+    transparent: Option<u8>,
+}
+
+impl Palette {
+    /// Returns a builder for a new palette
+    pub fn builder() -> PaletteBuilder {
+        PaletteBuilder::default()
+    }
+
+    /// Returns the colours of the palette
+    pub fn colours(&self) -> &[[u8; 3]] {
+        &self.colours
+    }
+
+    /// Returns the number of colours in the palette
+    pub fn len(&self) -> usize {
+        self.colours.len()
+    }
+
+    /// Returns true if the palette has no colours
+    pub fn is_empty(&self) -> bool {
+        self.colours.is_empty()
+    }
+
+    /// Returns the indexes of a named entry
+    pub fn get(&self, name: &str) -> Option<RangeInclusive<u8>> {
+        self.names.get(name).cloned()
+    }
+
+    /// Returns the index reserved as transparent, if any
+    pub fn transparent(&self) -> Option<u8> {
+        self.transparent
+    }
+
+    /// Returns the index of a named entry, or the first index of a gradient
+    ///
+    /// Panics if there is no entry with the name
+    pub fn index(&self, name: &str) -> u8 {
+        match self.names.get(name) {
+            Some(range) => *range.start(),
+            None => panic!("No palette entry named {name}"),
+        }
+    }
+}
+
+impl AsRef<[[u8; 3]]> for Palette {
+    fn as_ref(&self) -> &[[u8; 3]] {
+        &self.colours
+    }
+}
+
+/// Builder for a [`Palette`], adding entries in index order
+#[derive(Debug, Clone, Default)]
+pub struct PaletteBuilder {
+    entries: Vec<(String, Vec<[u8; 3]>)>,
+    transparent: Vec<String>,
+}
+
+impl PaletteBuilder {
+    /// Adds a single named colour
+    pub fn colour(self, name: &str, colour: [u8; 3]) -> Self {
+        self.entry(name, vec![colour])
+    }
+
+    /// Adds a linear gradient of `steps` colours from one colour to another inclusive
+    pub fn gradient(self, name: &str, from: [u8; 3], to: [u8; 3], steps: usize) -> Self {
+        self.stops(name, &[from, to], steps)
+    }
+
+    /// Adds a gradient of `steps` colours through evenly spaced colour stops
+    pub fn stops(self, name: &str, stops: &[[u8; 3]], steps: usize) -> Self {
+        let colours = (0..steps)
+            .map(|step| interpolate(stops, step, steps))
+            .collect();
+
+        self.entry(name, colours)
+    }
+
+    /// Adds `steps` colours sampled evenly from a perceptual ramp
+    pub fn ramp(self, name: &str, ramp: Ramp, steps: usize) -> Self {
+        self.stops(name, ramp.stops(), steps)
+    }
+
+    /// Reserves a named index as transparent
+    ///
+    /// Pass it to [`Gif::transparent`](super::Gif::transparent) to write it for pixels
+    /// which have not changed since the last frame, so frames should not draw with it.
+    pub fn transparent(mut self, name: &str) -> Self {
+        self.transparent.push(name.to_string());
+        self.colour(name, [0, 0, 0])
+    }
+
+    /// Builds the palette, checking the names are unique and it fits in a GIF
+    pub fn build(self) -> Result<Palette, PaletteError> {
+        let count = self.entries.iter().map(|(_, colours)| colours.len()).sum();
+
+        if count > MAX_COLOURS {
+            return Err(PaletteError::TooManyColours { count });
+        }
+
+        let mut colours = Vec::with_capacity(count);
+        let mut names = HashMap::new();
+
+        for (name, entry) in self.entries {
+            if entry.is_empty() {
+                return Err(PaletteError::Empty { name });
+            }
+
+            let range = colours.len() as u8..=(colours.len() + entry.len() - 1) as u8;
+
+            if names.contains_key(&name) {
+                return Err(PaletteError::DuplicateName { name });
+            }
+
+            names.insert(name, range);
+            colours.extend(entry);
+        }
+
+        let transparent = match self.transparent.as_slice() {
+            [] => None,
+            [name] => Some(*names[name].start()),
+            _ => return Err(PaletteError::MultipleTransparent),
+        };
+
+        Ok(Palette {
+            colours,
+            names,
+            transparent,
+        })
+    }
+
+    /// Adds a named run of colours
+    fn entry(mut self, name: &str, colours: Vec<[u8; 3]>) -> Self {
+        self.entries.push((name.to_string(), colours));
+        self
+    }
+}
+
+/// Returns colour `step` of `steps` evenly spaced through a list of colour stops
+fn interpolate(stops: &[[u8; 3]], step: usize, steps: usize) -> [u8; 3] {
+    match stops {
+        [] => [0; 3],
+        [colour] => *colour,
+        _ => {
+            // Position along the stops
+            let pos = if steps > 1 {
+                step as f64 * (stops.len() - 1) as f64 / (steps - 1) as f64
+            } else {
+                0.0
+            };
+
+            let segment = (pos as usize).min(stops.len() - 2);
+            let t = pos - segment as f64;
+
+            let (a, b) = (stops[segment], stops[segment + 1]);
+
+            [0, 1, 2].map(|c| (a[c] as f64 + (b[c] as f64 - a[c] as f64) * t).round() as u8)
+        }
+    }
+}
@@ -3,7 +3,19 @@ use std::{
     ops::RangeInclusive,
 };
 
+use super::Canvas;
+
+/// Changed pixels closer than this are grouped in to the same region
+const MERGE_GAP: u16 = 4;
+
+/// Cost in pixels of writing an extra frame, covering the frame headers
+const FRAME_COST: usize = 256;
+
+/// Maximum number of regions to write for a single frame
+const MAX_REGIONS: usize = 32;
+
 /// Structure describing a rectanglar region
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Region {
     top: u16,
     left: u16,
@@ -42,6 +54,29 @@ impl Region {
         (self.bottom - self.top) + 1
     }
 
+    /// Returns the number of pixels in the region
+    pub fn area(&self) -> usize {
+        self.width() as usize * self.height() as usize
+    }
+
+    /// Returns the smallest region containing both regions
+    pub fn union(&self, other: &Region) -> Region {
+        Region {
+            top: min(self.top, other.top),
+            left: min(self.left, other.left),
+            bottom: max(self.bottom, other.bottom),
+            right: max(self.right, other.right),
+        }
+    }
+
+    /// Returns true if the regions overlap or are separated by at most `gap` pixels
+    pub fn near(&self, other: &Region, gap: u16) -> bool {
+        self.left <= other.right.saturating_add(gap)
+            && other.left <= self.right.saturating_add(gap)
+            && self.top <= other.bottom.saturating_add(gap)
+            && other.top <= self.bottom.saturating_add(gap)
+    }
+
     /// Returns true if the y coordinate is contained in the region
     pub fn contains_y(&self, y: u16) -> bool {
         y >= self.top && y <= self.bottom
@@ -51,27 +86,118 @@ impl Region {
     pub fn x_range(&self) -> RangeInclusive<usize> {
         (self.left as usize)..=(self.right as usize)
     }
+}
+
+/// Returns regions covering the pixels that differ between two frames
+///
+/// Nearby changes are clustered in to regions, which are merged wherever writing
+/// one larger frame is cheaper than writing two. Falls back to a single bounding
+/// region if that is cheaper overall. Returns an empty list if the frames are the same.
+pub fn dirty_regions(last_frame: &Canvas, frame: &Canvas) -> Vec<Region> {
+    let mut active: Vec<Region> = Vec::new();
+    let mut regions = Vec::new();
+
+    for (y, (l1, l2)) in last_frame.rows().zip(frame.rows()).enumerate() {
+        let y = y as u16;
+
+        // Retire regions too far above this row to grow any further
+        active.retain(|region| {
+            let keep = region.bottom.saturating_add(MERGE_GAP) >= y;
+
+            if !keep {
+                regions.push(*region);
+            }
+
+            keep
+        });
+
+        // Find runs of changed pixels in the row
+        let mut x = 0;
+
+        while x < l1.len() {
+            if l1[x] == l2[x] {
+                x += 1;
+                continue;
+            }
+
+            let start = x;
+            let mut end = x;
+
+            while x < l1.len() && x <= end + MERGE_GAP as usize {
+                if l1[x] != l2[x] {
+                    end = x;
+                }
 
-    /// Initialises a region for the max region calculation
-    pub fn max_init() -> Self {
-        Self {
-            top: u16::MAX,
-            left: u16::MAX,
-            bottom: 0,
-            right: 0,
+                x += 1;
+            }
+
+            // Join the run with any nearby active regions
+            let mut run = Region::new(y, start as u16, y, end as u16);
+
+            active.retain(|region| {
+                let near = region.near(&run, MERGE_GAP);
+
+                if near {
+                    run = run.union(region);
+                }
+
+                !near
+            });
+
+            active.push(run);
         }
     }
 
-    /// Adds a coordinate in the max region calculation
-    pub fn max_add(&mut self, x: u16, y: u16) {
-        self.top = min(self.top, y);
-        self.left = min(self.left, x);
-        self.bottom = max(self.bottom, y);
-        self.right = max(self.right, x);
+    regions.extend(active);
+
+    merge_regions(&mut regions);
+
+    // Use a single region if there are too many or it is cheaper
+    if let Some(bounds) = regions.iter().copied().reduce(|a, b| a.union(&b)) {
+        let total = regions.iter().map(cost).sum::<usize>();
+
+        if regions.len() > MAX_REGIONS || cost(&bounds) <= total {
+            return vec![bounds];
+        }
     }
 
-    /// Returns true if max has been calculated successfully
-    pub fn max_valid(&self) -> bool {
-        self.top != u16::MAX
+    regions
+}
+
+/// Merges pairs of regions wherever one region covering both is no more costly
+fn merge_regions(regions: &mut Vec<Region>) {
+    loop {
+        let mut merged = false;
+        let mut i = 0;
+
+        while i < regions.len() {
+            let mut j = i + 1;
+
+            while j < regions.len() {
+                let union = regions[i].union(&regions[j]);
+
+                if cost(&union) <= cost(&regions[i]) + cost(&regions[j]) {
+                    regions[i] = union;
+                    regions.swap_remove(j);
+                    merged = true;
+
+                    // The grown region may now merge with earlier candidates
+                    j = i + 1;
+                } else {
+                    j += 1;
+                }
+            }
+
+            i += 1;
+        }
+
+        if !merged {
+            break;
+        }
     }
 }
+
+/// Returns the estimated cost of writing a region as a frame
+fn cost(region: &Region) -> usize {
+    region.area() + FRAME_COST
+}
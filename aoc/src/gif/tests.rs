@@ -0,0 +1,403 @@
+use super::*;
+use crate::test_util::TempDir;
+
+/// Renders a canvas as text, one character per pixel
+fn render(canvas: &Canvas) -> Vec<String> {
+    canvas
+        .rows()
+        .map(|row| row.iter().map(|&p| char::from(b'0' + p)).collect())
+        .collect()
+}
+
+#[test]
+fn rect_clipped() {
+    let mut canvas = Canvas::new(4, 3);
+
+    canvas.fill(1);
+    canvas.rect(-1, 1, 3, 5, 2);
+    canvas.rect(3, -2, 1, 3, 3);
+    canvas.rect(5, 0, 2, 2, 4);
+
+    assert_eq!(render(&canvas), ["1113", "2211", "2211"]);
+}
+
+#[test]
+fn line() {
+    let mut canvas = Canvas::new(5, 3);
+
+    canvas.line(0, 0, 4, 2, 1);
+    canvas.line(4, 0, 4, -5, 2);
+
+    assert_eq!(render(&canvas), ["10002", "01100", "00011"]);
+
+    // Lines mostly off the canvas are clipped before stepping
+    let mut canvas = Canvas::new(5, 3);
+
+    canvas.line(0, 0, i32::MAX, 0, 1);
+    canvas.line(i32::MIN, i32::MIN, -2, 2, 2);
+    canvas.line(-2, 4, 2, -4, 3);
+    canvas.line(i32::MAX, 2, i32::MIN, 2, 4);
+
+    assert_eq!(render(&canvas), ["31111", "30000", "44444"]);
+}
+
+#[test]
+fn line_matches_bresenham() {
+    // Reference implementation stepping every point
+    fn bresenham(x0: i32, y0: i32, x1: i32, y1: i32) -> Vec<(i32, i32)> {
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+        let (mut x, mut y, mut err) = (x0, y0, dx + dy);
+        let mut points = vec![(x, y)];
+
+        while x != x1 || y != y1 {
+            let e2 = 2 * err;
+
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+
+            points.push((x, y));
+        }
+
+        points
+    }
+
+    let ends = [-9, -4, -1, 0, 2, 5, 7, 12];
+
+    for &x0 in &ends {
+        for &y0 in &ends {
+            for &x1 in &ends {
+                for &y1 in &ends {
+                    let mut expected = Canvas::new(8, 6);
+
+                    for (x, y) in bresenham(x0, y0, x1, y1) {
+                        expected.set(x, y, 1);
+                    }
+
+                    let mut canvas = Canvas::new(8, 6);
+                    canvas.line(x0, y0, x1, y1, 1);
+
+                    assert_eq!(canvas, expected, "Line ({x0}, {y0}) to ({x1}, {y1})");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn rect_overflow() {
+    let mut canvas = Canvas::new(3, 2);
+
+    canvas.rect(i32::MAX - 1, 0, u16::MAX, 1, 1);
+    canvas.rect(1, i32::MAX - 1, 1, u16::MAX, 1);
+    canvas.rect(-1, -1, 2, 2, 2);
+
+    assert_eq!(render(&canvas), ["200", "000"]);
+}
+
+#[test]
+#[should_panic(expected = "Canvas rows must be at most 65535 pixels long")]
+fn canvas_from_long_rows() {
+    let _ = Canvas::from(vec![vec![0; 65536]]);
+}
+
+#[test]
+fn circle() {
+    let mut canvas = Canvas::new(7, 7);
+
+    canvas.circle(3, 3, 2, 1);
+    canvas.circle(0, 0, 1, 2);
+
+    assert_eq!(
+        render(&canvas),
+        ["2200000", "2211100", "0111110", "0111110", "0111110", "0011100", "0000000"]
+    );
+
+    // Large radii cover the whole canvas
+    canvas.circle(3, 3, u16::MAX, 3);
+
+    assert!(canvas.pixels().iter().all(|&pixel| pixel == 3));
+}
+
+#[test]
+fn blit() {
+    const SPRITE: [[u8; 3]; 2] = [[0, 5, 0], [5, 6, 5]];
+
+    let mut canvas = Canvas::new(4, 3);
+
+    canvas.fill(1);
+    canvas.blit(-1, 0, &SPRITE, Some(0));
+    canvas.blit(2, 2, &SPRITE, None);
+    canvas.stamp(2, 0, &SPRITE, 9);
+
+    assert_eq!(render(&canvas), ["5119", "6599", "1105"]);
+
+    let mut tile = Canvas::new(2, 2);
+    tile.set(1, 1, 7);
+    canvas.blit_canvas(0, 0, &tile, Some(0));
+
+    assert_eq!(render(&canvas), ["5119", "6799", "1105"]);
+}
+
+#[test]
+fn from_rows() {
+    let canvas = Canvas::from(vec![vec![1, 2], vec![3, 4]]);
+
+    assert_eq!((canvas.width(), canvas.height()), (2, 2));
+    assert_eq!(canvas.row(1), [3, 4]);
+    assert_eq!(canvas.get(1, 0), Some(2));
+    assert_eq!(canvas.get(2, 0), None);
+}
+
+#[test]
+fn palette_gradient() {
+    let palette = Palette::builder()
+        .colour("empty", [0, 0, 0])
+        .gradient("fade", [0, 0x10, 0xff], [0xff, 0x10, 0], 4)
+        .stops("heat", &[[0, 0, 0], [0xff, 0, 0], [0xff, 0xff, 0]], 5)
+        .build()
+        .unwrap();
+
+    assert_eq!(palette.len(), 10);
+    assert_eq!(palette.index("empty"), 0);
+    assert_eq!(palette.index("fade"), 1);
+    assert_eq!(palette.get("heat"), Some(5..=9));
+    assert_eq!(palette.get("missing"), None);
+
+    assert_eq!(
+        &palette.colours()[1..5],
+        [
+            [0, 0x10, 0xff],
+            [0x55, 0x10, 0xaa],
+            [0xaa, 0x10, 0x55],
+            [0xff, 0x10, 0]
+        ]
+    );
+    assert_eq!(
+        &palette.colours()[5..],
+        [
+            [0, 0, 0],
+            [0x80, 0, 0],
+            [0xff, 0, 0],
+            [0xff, 0x80, 0],
+            [0xff, 0xff, 0]
+        ]
+    );
+}
+
+#[test]
+fn palette_ramp() {
+    let palette = Palette::builder()
+        .ramp("viridis", Ramp::Viridis, 3)
+        .ramp("magma", Ramp::Magma, 2)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        palette.colours(),
+        [
+            [0x44, 0x01, 0x54],
+            [0x21, 0x91, 0x8c],
+            [0xfd, 0xe7, 0x25],
+            [0x00, 0x00, 0x04],
+            [0xfc, 0xfd, 0xbf]
+        ]
+    );
+}
+
+#[test]
+fn palette_invalid() {
+    assert_eq!(
+        Palette::builder()
+            .colour("empty", [0, 0, 0])
+            .ramp("magma", Ramp::Magma, 256)
+            .build(),
+        Err(PaletteError::TooManyColours { count: 257 })
+    );
+
+    assert_eq!(
+        Palette::builder()
+            .colour("paper", [0, 0, 0])
+            .colour("paper", [0xff, 0xff, 0xff])
+            .build(),
+        Err(PaletteError::DuplicateName {
+            name: "paper".into()
+        })
+    );
+
+    assert_eq!(
+        Palette::builder()
+            .gradient("fade", [0; 3], [0xff; 3], 0)
+            .build(),
+        Err(PaletteError::Empty {
+            name: "fade".into()
+        })
+    );
+
+    let full = Palette::builder()
+        .ramp("viridis", Ramp::Viridis, 256)
+        .build();
+
+    assert_eq!(
+        full.map(|palette| palette.get("viridis")),
+        Ok(Some(0..=255))
+    );
+}
+
+#[test]
+fn dirty_regions_clustered() {
+    let last = Canvas::new(40, 40);
+    let mut frame = last.clone();
+
+    assert_eq!(region::dirty_regions(&last, &frame), []);
+
+    // Changes in opposite corners are written separately
+    frame.rect(0, 0, 3, 2, 1);
+    frame.set(2, 4, 1);
+    frame.rect(36, 37, 4, 3, 2);
+
+    assert_eq!(
+        region::dirty_regions(&last, &frame),
+        [Region::new(0, 0, 4, 2), Region::new(37, 36, 39, 39)]
+    );
+
+    // Close changes are cheaper as one region
+    let mut frame = last.clone();
+
+    frame.set(5, 5, 1);
+    frame.set(12, 14, 1);
+
+    assert_eq!(
+        region::dirty_regions(&last, &frame),
+        [Region::new(5, 5, 14, 12)]
+    );
+}
+
+/// Writes frames to a GIF, pipelined to the given depth if set, and decodes its
+/// frames as (region, delay, transparent, pixels)
+fn write_and_decode(
+    name: &str,
+    palette: &Palette,
+    frames: &[Canvas],
+    pipeline: Option<usize>,
+) -> Vec<(Region, u16, Option<u8>, Vec<u8>)> {
+    let dir = TempDir::new(&format!("gif-{name}"));
+    let path = dir.path().join("out.gif");
+
+    let mut gif = Gif::new(
+        path.to_str().unwrap(),
+        palette,
+        frames[0].width(),
+        frames[0].height(),
+        1,
+        1,
+    )
+    .unwrap();
+
+    if let Some(index) = palette.transparent() {
+        gif = gif.transparent(index);
+    }
+
+    if let Some(depth) = pipeline {
+        gif = gif.pipelined(depth);
+    }
+
+    for frame in frames {
+        gif.draw_frame(frame.clone(), 5).unwrap();
+    }
+
+    gif.finish().unwrap();
+
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::Indexed);
+
+    let mut decoder = options
+        .read_info(std::fs::File::open(&path).unwrap())
+        .unwrap();
+    let mut decoded = Vec::new();
+
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        decoded.push((
+            Region::new(
+                frame.top,
+                frame.left,
+                frame.top + frame.height - 1,
+                frame.left + frame.width - 1,
+            ),
+            frame.delay,
+            frame.transparent,
+            frame.buffer.to_vec(),
+        ));
+    }
+
+    decoded
+}
+
+#[test]
+fn transparent_unchanged() {
+    let palette = Palette::builder()
+        .colour("empty", [0, 0, 0])
+        .colour("paper", [0xff, 0xff, 0xff])
+        .transparent("unchanged")
+        .build()
+        .unwrap();
+
+    assert_eq!(palette.transparent(), Some(2));
+
+    let first = Canvas::new(4, 2);
+    let mut second = first.clone();
+
+    second.set(0, 0, 1);
+    second.set(2, 0, 1);
+
+    let frames = write_and_decode("transparent", &palette, &[first, second], None);
+
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].2, None);
+    assert_eq!(
+        frames[1],
+        (Region::new(0, 0, 0, 2), 5, Some(2), vec![1, 2, 1])
+    );
+
+    // Only one index may be transparent
+    assert_eq!(
+        Palette::builder()
+            .transparent("unchanged")
+            .transparent("clear")
+            .build(),
+        Err(PaletteError::MultipleTransparent)
+    );
+}
+
+#[test]
+fn pipelined() {
+    let palette = Palette::builder()
+        .ramp("viridis", Ramp::Viridis, 16)
+        .build()
+        .unwrap();
+
+    // Circles growing from opposite corners
+    let frames = (0..20u16)
+        .map(|i| {
+            let mut canvas = Canvas::new(64, 48);
+
+            canvas.circle(0, 0, i, (i % 16) as u8);
+            canvas.circle(63, 47, i / 2, 15 - (i % 16) as u8);
+
+            canvas
+        })
+        .collect::<Vec<_>>();
+
+    let direct = write_and_decode("direct", &palette, &frames, None);
+    let pipelined = write_and_decode("pipelined", &palette, &frames, Some(2));
+
+    assert!(direct.len() >= frames.len());
+    assert_eq!(direct, pipelined);
+}
@@ -0,0 +1,199 @@
+use std::{borrow::Cow, cmp::max, fs::File, iter};
+
+use gif::{Encoder, EncodingError, Frame};
+
+use super::{region, Canvas, Command, IdenticalAction, Region};
+
+/// Diffs, scales and encodes frames in to a GIF file
+pub(super) struct Writer {
+    width: u16,
+    height: u16,
+    x_scale: u16,
+    y_scale: u16,
+    encoder: Encoder<File>,
+    last_frame: Option<Canvas>,
+    transparent: Option<u8>,
+}
+
+impl Writer {
+    /// Creates a new writer for frames of a given size and scale
+    pub(super) fn new(
+        encoder: Encoder<File>,
+        width: u16,
+        height: u16,
+        x_scale: u16,
+        y_scale: u16,
+    ) -> Self {
+        Self {
+            width,
+            height,
+            x_scale,
+            y_scale,
+            encoder,
+            last_frame: None,
+            transparent: None,
+        }
+    }
+
+    /// Sets the index written for pixels unchanged since the last frame
+    pub(super) fn set_transparent(&mut self, transparent: u8) {
+        self.transparent = Some(transparent);
+    }
+
+    /// Carries out a drawing command
+    pub(super) fn run(&mut self, command: Command) -> Result<(), EncodingError> {
+        let frames = self.frames(command);
+
+        self.write(frames)
+    }
+
+    /// Returns the GIF frames for a drawing command
+    ///
+    /// A drawn frame is split in to a sub-frame for each changed region, with the
+    /// delay on the last.
+    pub(super) fn frames(&mut self, command: Command) -> Vec<Frame<'static>> {
+        let (frame_data, delay, identical_action) = match command {
+            Command::Frame(frame_data, delay, identical_action) => {
+                (frame_data, delay, identical_action)
+            }
+            Command::Delay(delay) => return vec![delay_frame(delay)],
+        };
+
+        // Calculate the difference between this frame and the last
+        let regions = self.frame_difference(&frame_data);
+
+        if regions.is_empty() {
+            // No difference
+            return match identical_action {
+                IdenticalAction::Delay => vec![delay_frame(delay)],
+                IdenticalAction::Ignore => Vec::new(),
+            };
+        }
+
+        let last = regions.len() - 1;
+
+        let frames = regions
+            .iter()
+            .enumerate()
+            .map(|(i, region)| {
+                let (out_section, transparent) = self.scale_region(&frame_data, region);
+
+                Frame {
+                    top: region.top() * self.y_scale,
+                    left: region.left() * self.x_scale,
+                    width: region.width() * self.x_scale,
+                    height: region.height() * self.y_scale,
+                    buffer: Cow::Owned(out_section),
+                    delay: if i == last { max(2, delay) } else { 0 },
+                    transparent,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        // Save the last frame
+        self.last_frame = Some(frame_data);
+
+        frames
+    }
+
+    /// Compresses and writes out frames
+    #[cfg(not(feature = "parallel"))]
+    pub(super) fn write(&mut self, frames: Vec<Frame>) -> Result<(), EncodingError> {
+        for frame in frames {
+            self.encoder.write_frame(&frame)?;
+        }
+
+        Ok(())
+    }
+
+    /// Compresses frames on the rayon thread pool and writes them out
+    #[cfg(feature = "parallel")]
+    pub(super) fn write(&mut self, mut frames: Vec<Frame>) -> Result<(), EncodingError> {
+        use rayon::prelude::*;
+
+        frames.par_iter_mut().for_each(Frame::make_lzw_pre_encoded);
+
+        for frame in frames {
+            self.encoder.write_lzw_pre_encoded_frame(&frame)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the GIF trailer and closes the file
+    pub(super) fn finish(self) -> Result<(), EncodingError> {
+        self.encoder.into_inner()?;
+
+        Ok(())
+    }
+
+    /// Returns the pixels of a region of a frame scaled up to the GIF size
+    ///
+    /// Pixels unchanged since the last frame are replaced with the transparent index
+    /// if there is one, which is returned for the frame.
+    fn scale_region(&self, frame_data: &Canvas, region: &Region) -> (Vec<u8>, Option<u8>) {
+        let unchanged = self.transparent.zip(self.last_frame.as_ref());
+
+        let mut out =
+            Vec::with_capacity(region.area() * self.x_scale as usize * self.y_scale as usize);
+
+        for (y, line) in frame_data.rows().enumerate() {
+            if !region.contains_y(y as u16) {
+                continue;
+            }
+
+            let line = &line[region.x_range()];
+
+            // Expand the line horizontally
+            let start = out.len();
+
+            match unchanged {
+                Some((transparent, last_frame)) => {
+                    for (&pix, last) in line.iter().zip(&last_frame.row(y as u16)[region.x_range()])
+                    {
+                        let pix = if pix == *last { transparent } else { pix };
+
+                        out.extend(iter::repeat_n(pix, self.x_scale as usize));
+                    }
+                }
+                None => {
+                    for &pix in line {
+                        out.extend(iter::repeat_n(pix, self.x_scale as usize));
+                    }
+                }
+            }
+
+            // Repeat it vertically
+            for _ in 1..self.y_scale {
+                out.extend_from_within(start..);
+            }
+        }
+
+        (out, unchanged.map(|(transparent, _)| transparent))
+    }
+
+    /// Calculates the regions that differ between a given frame and the last frame output
+    /// Returns an empty list if there is no difference
+    /// If there is no previous frame then the whole frame is considered changed
+    fn frame_difference(&self, frame_data: &Canvas) -> Vec<Region> {
+        if let Some(last_frame) = &self.last_frame {
+            region::dirty_regions(last_frame, frame_data)
+        } else {
+            // No previous frame
+            vec![Region::new(0, 0, self.height - 1, self.width - 1)]
+        }
+    }
+}
+
+/// Returns an empty frame which only adds a delay
+fn delay_frame(delay: u16) -> Frame<'static> {
+    Frame {
+        delay: max(2, delay),
+        width: 1,
+        height: 1,
+        transparent: Some(0),
+        buffer: Cow::Owned(vec![0]),
+        ..Default::default()
+    }
+}
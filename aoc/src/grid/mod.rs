@@ -0,0 +1,187 @@
+use std::ops::{Index, IndexMut};
+
+/// Offsets of the 4 orthogonal neighbours of a cell
+const OFFSETS4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets of the 8 surrounding neighbours of a cell, in row major order
+const OFFSETS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Two dimensional grid stored in row major order
+///
+/// Cells are indexed by `(x, y)` coordinate tuples
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from a vector of cells in row major order
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "Grid size mismatch");
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid with every cell set to a value
+    pub fn from_elem(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Returns the width of the grid
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the grid
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns a reference to a cell, or None if out of bounds
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[(y * self.width) + x])
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to a cell, or None if out of bounds
+    #[inline]
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[(y * self.width) + x])
+        } else {
+            None
+        }
+    }
+
+    /// Returns a row of the grid as a slice
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[(y * self.width)..((y + 1) * self.width)]
+    }
+
+    /// Returns a row of the grid as a mutable slice
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[(y * self.width)..((y + 1) * self.width)]
+    }
+
+    /// Returns an iterator over the rows of the grid
+    ///
+    /// A grid with zero width still has `height` rows, each empty
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Returns an iterator over a column of the grid
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column out of bounds");
+
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Returns an iterator over the coordinates and values of every cell in row major order
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    /// Returns the coordinates of the first cell matching a value in row major order
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.position(|cell| cell == value)
+    }
+
+    /// Returns the coordinates of the first cell matching a predicate in row major order
+    pub fn position<P>(&self, pred: P) -> Option<(usize, usize)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.cells
+            .iter()
+            .position(pred)
+            .map(|i| (i % self.width, i / self.width))
+    }
+
+    /// Returns an iterator over the coordinates of the orthogonal neighbours of a cell
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours(x, y, &OFFSETS4)
+    }
+
+    /// Returns an iterator over the coordinates of the 8 surrounding neighbours of a cell
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours(x, y, &OFFSETS8)
+    }
+
+    /// Returns an iterator over neighbour coordinates within the grid for a set of offsets
+    fn neighbours(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        let height = self.height;
+
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let nx = x.checked_add_signed(dx)?;
+            let ny = y.checked_add_signed(dy)?;
+
+            if nx < width && ny < height {
+                Some((nx, ny))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        assert!(x < self.width, "Grid x out of bounds");
+
+        &self.cells[(y * self.width) + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        assert!(x < self.width, "Grid x out of bounds");
+
+        &mut self.cells[(y * self.width) + x]
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,83 @@
+use super::*;
+
+/// Returns a 3x2 grid numbered in row major order
+fn grid() -> Grid<u8> {
+    Grid::new(3, 2, (0..6).collect())
+}
+
+#[test]
+fn bounds() {
+    let mut grid = grid();
+
+    assert_eq!(grid.get(2, 1), Some(&5));
+    assert_eq!(grid.get(3, 0), None);
+    assert_eq!(grid.get(0, 2), None);
+    assert_eq!(grid.get_mut(3, 1), None);
+
+    grid[(1, 1)] = 9;
+
+    assert_eq!(grid[(1, 1)], 9);
+}
+
+#[test]
+#[should_panic(expected = "Grid x out of bounds")]
+fn index_x_bounds() {
+    let _ = grid()[(3, 0)];
+}
+
+#[test]
+#[should_panic(expected = "Grid size mismatch")]
+fn size_mismatch() {
+    Grid::new(2, 2, vec![0; 3]);
+}
+
+#[test]
+fn rows() {
+    let grid = grid();
+
+    assert_eq!(
+        grid.rows().collect::<Vec<_>>(),
+        vec![&[0, 1, 2], &[3, 4, 5]]
+    );
+    assert_eq!(grid.row(1), &[3, 4, 5]);
+    assert_eq!(grid.column(1).collect::<Vec<_>>(), vec![&1, &4]);
+
+    // Zero width grids have empty rows
+    let empty = Grid::<u8>::new(0, 3, Vec::new());
+
+    assert_eq!(empty.rows().collect::<Vec<_>>(), vec![&[] as &[u8]; 3]);
+
+    // Zero height grids have no rows
+    let empty = Grid::<u8>::new(3, 0, Vec::new());
+
+    assert_eq!(empty.rows().count(), 0);
+}
+
+#[test]
+fn neighbours() {
+    let grid = grid();
+
+    assert_eq!(
+        grid.neighbours4(0, 0).collect::<Vec<_>>(),
+        vec![(1, 0), (0, 1)]
+    );
+    assert_eq!(
+        grid.neighbours4(1, 1).collect::<Vec<_>>(),
+        vec![(1, 0), (0, 1), (2, 1)]
+    );
+    assert_eq!(
+        grid.neighbours8(2, 0).collect::<Vec<_>>(),
+        vec![(1, 0), (1, 1), (2, 1)]
+    );
+    assert_eq!(grid.neighbours8(1, 0).count(), 5);
+}
+
+#[test]
+fn search() {
+    let grid = grid();
+
+    assert_eq!(grid.find(&4), Some((1, 1)));
+    assert_eq!(grid.find(&7), None);
+    assert_eq!(grid.position(|&cell| cell > 2), Some((0, 1)));
+    assert_eq!(grid.iter().nth(5), Some(((2, 1), &5)));
+}
@@ -0,0 +1,65 @@
+use std::{fs::File, path::PathBuf};
+
+use crate::Error;
+
+/// Backend which loads the contents of a regular input file
+pub trait Backend: AsRef<[u8]> + Sized {
+    /// Loads an open file
+    fn load(file: File, path: PathBuf) -> Result<Self, Error>;
+}
+
+/// Memory mapped file backend
+#[cfg(not(any(miri, feature = "owned-input")))]
+pub struct MmapBackend(memmap2::Mmap);
+
+#[cfg(not(any(miri, feature = "owned-input")))]
+impl Backend for MmapBackend {
+    fn load(file: File, path: PathBuf) -> Result<Self, Error> {
+        // SAFETY: the mapping is read only, and input files are assumed not to be
+        // changed by other processes while they are mapped
+        let mmap =
+            unsafe { memmap2::Mmap::map(&file) }.map_err(|source| Error::Mmap { path, source })?;
+
+        Ok(Self(mmap))
+    }
+}
+
+#[cfg(not(any(miri, feature = "owned-input")))]
+impl AsRef<[u8]> for MmapBackend {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+/// Owned buffer backend, used under Miri or with the `owned-input` feature
+#[cfg(any(miri, feature = "owned-input"))]
+pub struct OwnedBackend(Vec<u8>);
+
+#[cfg(any(miri, feature = "owned-input"))]
+impl Backend for OwnedBackend {
+    fn load(mut file: File, path: PathBuf) -> Result<Self, Error> {
+        use std::io::Read;
+
+        let mut buf = Vec::new();
+
+        file.read_to_end(&mut buf)
+            .map_err(|source| Error::Io { path, source })?;
+
+        Ok(Self(buf))
+    }
+}
+
+#[cfg(any(miri, feature = "owned-input"))]
+impl AsRef<[u8]> for OwnedBackend {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Backend used to load input files
+#[cfg(not(any(miri, feature = "owned-input")))]
+pub type FileBackend = MmapBackend;
+
+/// Backend used to load input files
+#[cfg(any(miri, feature = "owned-input"))]
+pub type FileBackend = OwnedBackend;
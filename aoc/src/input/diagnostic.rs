@@ -0,0 +1,207 @@
+use std::{
+    cell::{Cell, RefCell},
+    error::Error as StdError,
+    fmt,
+    panic::{self, AssertUnwindSafe, PanicHookInfo},
+    path::Path,
+    sync::{Mutex, PoisonError, RwLock},
+    thread,
+};
+
+use super::BoxError;
+
+thread_local! {
+    /// True while a transform is running under catch_panic
+    static CAPTURING: Cell<bool> = const { Cell::new(false) };
+    /// Location of the last panic captured on this thread
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Error raised when a transform panics
+#[derive(Debug)]
+pub struct PanicError {
+    message: String,
+    location: Option<String>,
+}
+
+impl fmt::Display for PanicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "panicked at {location}: {}", self.message),
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+impl StdError for PanicError {}
+
+/// Panic hook function, as returned by `panic::take_hook`
+type Hook = Box<dyn Fn(&PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+/// Number of live hook guards
+static HOOK_USERS: Mutex<usize> = Mutex::new(0);
+
+/// Hook which was installed before the capturing hook, restored when it is removed
+static PREV_HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+
+/// Keeps the capturing panic hook installed while alive
+///
+/// The hook is installed by the first guard, chaining to the previous hook for panics
+/// which aren't being captured, and the previous hook is restored when the last guard
+/// is dropped. Holding a guard around a loop of `catch_panic` calls avoids swapping the
+/// hook for each one.
+pub(crate) struct HookGuard {
+    /// False if the guard was created while panicking, when the hook can't be changed
+    counted: bool,
+}
+
+impl HookGuard {
+    /// Installs the capturing hook if it is not already installed
+    pub(crate) fn install() -> Self {
+        if thread::panicking() {
+            return Self { counted: false };
+        }
+
+        let mut users = HOOK_USERS.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if *users == 0 {
+            // The previous hook is stored before the capturing hook can call it
+            let prev = panic::take_hook();
+
+            *PREV_HOOK.write().unwrap_or_else(PoisonError::into_inner) = Some(prev);
+
+            panic::set_hook(Box::new(|info| {
+                if CAPTURING.get() {
+                    LOCATION.set(info.location().map(|l| l.to_string()));
+                } else if let Some(prev) = PREV_HOOK
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .as_ref()
+                {
+                    prev(info);
+                }
+            }));
+        }
+
+        *users += 1;
+
+        Self { counted: true }
+    }
+}
+
+impl Drop for HookGuard {
+    fn drop(&mut self) {
+        // The hook can't be changed while panicking, so leave it installed for good
+        if !self.counted || thread::panicking() {
+            return;
+        }
+
+        let mut users = HOOK_USERS.lock().unwrap_or_else(PoisonError::into_inner);
+
+        *users -= 1;
+
+        if *users == 0 {
+            // Remove the capturing hook before taking the previous one back out
+            drop(panic::take_hook());
+
+            let prev = PREV_HOOK
+                .write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .take();
+
+            if let Some(prev) = prev {
+                panic::set_hook(prev);
+            }
+        }
+    }
+}
+
+/// Runs a transform, converting a panic in to an error
+///
+/// The panic message is suppressed so the panic is only reported by the diagnostic
+/// for the error.
+pub(crate) fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, PanicError> {
+    let _hook = HookGuard::install();
+
+    let was_capturing = CAPTURING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CAPTURING.set(was_capturing);
+
+    result.map_err(|payload| {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Box<dyn Any>".to_string()
+        };
+
+        PanicError {
+            message,
+            location: LOCATION.take(),
+        }
+    })
+}
+
+/// Runs a fallible transform, converting a panic in to an error
+pub(crate) fn try_catch_panic<T, E>(f: impl FnOnce() -> Result<T, E>) -> Result<T, BoxError>
+where
+    E: Into<BoxError>,
+{
+    match catch_panic(f) {
+        Ok(result) => result.map_err(Into::into),
+        Err(panic) => Err(panic.into()),
+    }
+}
+
+/// Renders a diagnostic for an error on a line of the input
+///
+/// Shows the file name, line and column, the source line, and a caret under the
+/// offending token if its column is known.
+pub(crate) fn render(
+    f: &mut fmt::Formatter<'_>,
+    file: Option<&Path>,
+    line: usize,
+    column: Option<usize>,
+    width: usize,
+    text: &str,
+    message: &dyn fmt::Display,
+) -> fmt::Result {
+    let gutter = line.to_string().len();
+
+    writeln!(f, "{message}")?;
+
+    // Location
+    write!(f, "{:gutter$}--> ", "")?;
+
+    if let Some(file) = file {
+        write!(f, "{}:", file.display())?;
+    }
+
+    write!(f, "{line}")?;
+
+    if let Some(column) = column {
+        write!(f, ":{column}")?;
+    }
+
+    // Source line
+    writeln!(f)?;
+    writeln!(f, "{:gutter$} |", "")?;
+    write!(f, "{line} | {}", text.replace('\t', " "))?;
+
+    // Caret
+    if let Some(column) = column {
+        writeln!(f)?;
+        write!(
+            f,
+            "{:gutter$} | {:pad$}{:^<width$}",
+            "",
+            "",
+            "",
+            pad = column - 1,
+            width = width.max(1)
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use crate::{grid::Grid, Error};
+
+use super::{diagnostic::catch_panic, BoxError};
+
+/// Builds a grid from numbered lines, transforming each character to a cell
+///
+/// Panics in the transform are caught once around the whole build, and reported at
+/// the cell which was being transformed.
+pub(crate) fn build_grid<'a, T, E, F>(
+    lines: impl Iterator<Item = Result<(usize, &'a str), Error>>,
+    mut tfn: F,
+) -> Result<Grid<T>, Error>
+where
+    F: FnMut(char) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    // Line number, column and text of the cell being transformed
+    let mut current = (0, 0, "");
+
+    match catch_panic(|| build_cells(lines, &mut tfn, &mut current)) {
+        Ok(result) => result,
+        Err(panic) => Err(cell_error(current, panic.into())),
+    }
+}
+
+/// Transforms each character of the lines to a cell, recording the cell being transformed
+fn build_cells<'a, T, E, F>(
+    lines: impl Iterator<Item = Result<(usize, &'a str), Error>>,
+    tfn: &mut F,
+    current: &mut (usize, usize, &'a str),
+) -> Result<Grid<T>, Error>
+where
+    F: FnMut(char) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    let mut cells = Vec::new();
+    let mut width = None;
+    let mut height = 0;
+
+    for line in lines {
+        let (line_no, line) = line?;
+        let start = cells.len();
+
+        for (col, c) in line.chars().enumerate() {
+            *current = (line_no, col + 1, line);
+
+            cells.push(tfn(c).map_err(|e| cell_error(*current, e.into()))?);
+        }
+
+        let found = cells.len() - start;
+
+        match width {
+            None => width = Some(found),
+            Some(expected) if expected != found => {
+                return Err(Error::GridWidth {
+                    line: line_no,
+                    expected,
+                    found,
+                });
+            }
+            _ => (),
+        }
+
+        height += 1;
+    }
+
+    match width {
+        Some(width) => Ok(Grid::new(width, height, cells)),
+        None => Err(Error::EmptyInput),
+    }
+}
+
+/// Builds the error for a failed cell transform
+fn cell_error((line, column, text): (usize, usize, &str), source: BoxError) -> Error {
+    Error::Transform {
+        file: None,
+        line,
+        column: Some(column),
+        width: 1,
+        text: text.to_string(),
+        source,
+    }
+}
@@ -1,39 +1,614 @@
-use std::error::Error;
+use std::convert::Infallible;
 
-mod mmap;
-use mmap::Input;
+use crate::{grid::Grid, Error, Puzzle};
+
+mod backend;
+
+mod source;
+pub use source::{Input, INPUT_DIR_VAR, INPUT_FLAG, INPUT_VAR};
+
+pub(crate) mod diagnostic;
+pub use diagnostic::PanicError;
+use diagnostic::{catch_panic, try_catch_panic, HookGuard};
+
+mod grid;
+use grid::build_grid;
+
+#[cfg(feature = "parallel")]
+mod par;
+#[cfg(feature = "parallel")]
+use par::try_apply_vec_par;
+
+pub mod scan;
+use scan::Scanner;
+
+mod section;
+use section::Sections;
+pub use section::{Section, SectionTransform, TrySectionTransform};
+
+mod transform;
+use transform::{input_error, line_error};
+pub use transform::{BoxError, TokenError};
+
+mod utf8;
+
+mod variant;
+pub use variant::{find_variants, Variant, DEFAULT_VARIANT};
+
+// Each parse_input_* function has a parse_test_* twin which parses a string instead
+// of the input file. Both load an Input and share the same implementation, so tests
+// exercise exactly the same line handling as real runs. The parse_input_iter functions
+// take an already loaded Input so serve for both.
 
 /// Parse whole input file with with a given transform
-pub fn parse_input<T, F>(day: usize, mut tfn: F) -> Result<T, Box<dyn Error>>
+pub fn parse_input<T, F>(puzzle: impl Into<Puzzle>, tfn: F) -> Result<T, Error>
 where
     F: FnMut(&str) -> T,
 {
-    Ok(tfn(Input::new(day)?.as_str()?))
+    with_input(Input::new(puzzle), |input| apply(input, tfn))
+}
+
+/// Parse whole input string with with a given transform
+pub fn parse_test<T, F>(test: &str, tfn: F) -> Result<T, Error>
+where
+    F: FnMut(&str) -> T,
+{
+    with_input(Input::new_from_test(test), |input| apply(input, tfn))
+}
+
+/// Parse whole input file with with a given fallible transform
+pub fn try_parse_input<T, E, F>(puzzle: impl Into<Puzzle>, tfn: F) -> Result<T, Error>
+where
+    F: FnMut(&str) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    with_input(Input::new(puzzle), |input| try_apply(input, tfn))
+}
+
+/// Parse whole input string with with a given fallible transform
+pub fn try_parse_test<T, E, F>(test: &str, tfn: F) -> Result<T, Error>
+where
+    F: FnMut(&str) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    with_input(Input::new_from_test(test), |input| try_apply(input, tfn))
 }
 
 /// Parse an input file line by line to a vector with a given transform
-pub fn parse_input_vec<T, F>(day: usize, tfn: F) -> Result<Vec<T>, Box<dyn Error>>
+pub fn parse_input_vec<T, F>(puzzle: impl Into<Puzzle>, tfn: F) -> Result<Vec<T>, Error>
+where
+    F: FnMut(&str) -> T,
+{
+    with_input(Input::new(puzzle), |input| apply_vec(input, tfn))
+}
+
+/// Parse an input string line by line to a vector with a given transform
+pub fn parse_test_vec<T, F>(test: &str, tfn: F) -> Result<Vec<T>, Error>
 where
     F: FnMut(&str) -> T,
 {
-    Ok(Input::new(day)?.lines().map(tfn).collect())
+    with_input(Input::new_from_test(test), |input| apply_vec(input, tfn))
+}
+
+/// Parse an input file line by line to a vector with a given fallible transform
+pub fn try_parse_input_vec<T, E, F>(puzzle: impl Into<Puzzle>, tfn: F) -> Result<Vec<T>, Error>
+where
+    F: FnMut(&str) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    with_input(Input::new(puzzle), |input| try_apply_vec(input, tfn))
+}
+
+/// Parse an input string line by line to a vector with a given fallible transform
+pub fn try_parse_test_vec<T, E, F>(test: &str, tfn: F) -> Result<Vec<T>, Error>
+where
+    F: FnMut(&str) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    with_input(Input::new_from_test(test), |input| {
+        try_apply_vec(input, tfn)
+    })
+}
+
+/// Parse an input file line by line with a given transform, folding the values in to
+/// an accumulator without collecting them
+pub fn fold_input<T, A, F, G>(puzzle: impl Into<Puzzle>, tfn: F, init: A, f: G) -> Result<A, Error>
+where
+    F: FnMut(&str) -> T,
+    G: FnMut(A, T) -> A,
+{
+    with_input(Input::new(puzzle), |input| {
+        fold(apply_iter(input, tfn), init, f)
+    })
+}
+
+/// Parse an input string line by line with a given transform, folding the values in to
+/// an accumulator without collecting them
+pub fn fold_test<T, A, F, G>(test: &str, tfn: F, init: A, f: G) -> Result<A, Error>
+where
+    F: FnMut(&str) -> T,
+    G: FnMut(A, T) -> A,
+{
+    with_input(Input::new_from_test(test), |input| {
+        fold(apply_iter(input, tfn), init, f)
+    })
+}
+
+/// Parse an input file line by line with a given fallible transform, folding the values
+/// in to an accumulator without collecting them
+pub fn try_fold_input<T, E, A, F, G>(
+    puzzle: impl Into<Puzzle>,
+    tfn: F,
+    init: A,
+    f: G,
+) -> Result<A, Error>
+where
+    F: FnMut(&str) -> Result<T, E>,
+    E: Into<BoxError>,
+    G: FnMut(A, T) -> A,
+{
+    with_input(Input::new(puzzle), |input| {
+        fold(try_apply_iter(input, tfn), init, f)
+    })
+}
+
+/// Parse an input string line by line with a given fallible transform, folding the
+/// values in to an accumulator without collecting them
+pub fn try_fold_test<T, E, A, F, G>(test: &str, tfn: F, init: A, f: G) -> Result<A, Error>
+where
+    F: FnMut(&str) -> Result<T, E>,
+    E: Into<BoxError>,
+    G: FnMut(A, T) -> A,
+{
+    with_input(Input::new_from_test(test), |input| {
+        fold(try_apply_iter(input, tfn), init, f)
+    })
+}
+
+/// Returns an iterator applying a transform to each line of a loaded input
+///
+/// Lines are transformed as the iterator is advanced, borrowing from the input
+pub fn parse_input_iter<'a, T, F>(
+    input: &'a Input,
+    tfn: F,
+) -> impl Iterator<Item = Result<T, Error>> + 'a
+where
+    F: FnMut(&str) -> T + 'a,
+{
+    apply_iter(input, tfn).map(|value| value.map_err(|e| e.with_file(input.path())))
+}
+
+/// Returns an iterator applying a fallible transform to each line of a loaded input
+///
+/// Lines are transformed as the iterator is advanced, borrowing from the input
+pub fn try_parse_input_iter<'a, T, E, F>(
+    input: &'a Input,
+    tfn: F,
+) -> impl Iterator<Item = Result<T, Error>> + 'a
+where
+    F: FnMut(&str) -> Result<T, E> + 'a,
+    E: Into<BoxError>,
+{
+    try_apply_iter(input, tfn).map(|value| value.map_err(|e| e.with_file(input.path())))
+}
+
+/// Parse an input file line by line to a vector with a given transform, running the
+/// transform on the rayon thread pool
+#[cfg(feature = "parallel")]
+pub fn parse_input_vec_par<T, F>(puzzle: impl Into<Puzzle>, tfn: F) -> Result<Vec<T>, Error>
+where
+    F: Fn(&str) -> T + Sync,
+    T: Send,
+{
+    with_input(Input::new(puzzle), |input| {
+        try_apply_vec_par(input, |line| Ok::<_, Infallible>(tfn(line)))
+    })
+}
+
+/// Parse an input string line by line to a vector with a given transform, running the
+/// transform on the rayon thread pool
+#[cfg(feature = "parallel")]
+pub fn parse_test_vec_par<T, F>(test: &str, tfn: F) -> Result<Vec<T>, Error>
+where
+    F: Fn(&str) -> T + Sync,
+    T: Send,
+{
+    with_input(Input::new_from_test(test), |input| {
+        try_apply_vec_par(input, |line| Ok::<_, Infallible>(tfn(line)))
+    })
+}
+
+/// Parse an input file line by line to a vector with a given fallible transform, running
+/// the transform on the rayon thread pool
+#[cfg(feature = "parallel")]
+pub fn try_parse_input_vec_par<T, E, F>(puzzle: impl Into<Puzzle>, tfn: F) -> Result<Vec<T>, Error>
+where
+    F: Fn(&str) -> Result<T, E> + Sync,
+    E: Into<BoxError>,
+    T: Send,
+{
+    with_input(Input::new(puzzle), |input| try_apply_vec_par(input, tfn))
+}
+
+/// Parse an input string line by line to a vector with a given fallible transform, running
+/// the transform on the rayon thread pool
+#[cfg(feature = "parallel")]
+pub fn try_parse_test_vec_par<T, E, F>(test: &str, tfn: F) -> Result<Vec<T>, Error>
+where
+    F: Fn(&str) -> Result<T, E> + Sync,
+    E: Into<BoxError>,
+    T: Send,
+{
+    with_input(Input::new_from_test(test), |input| {
+        try_apply_vec_par(input, tfn)
+    })
+}
+
+/// Parse an input file line by line, including blank lines, to a vector with a given
+/// transform taking the 1-based line number and line
+pub fn parse_input_vec_numbered<T, F>(puzzle: impl Into<Puzzle>, tfn: F) -> Result<Vec<T>, Error>
+where
+    F: FnMut(usize, &str) -> T,
+{
+    with_input(Input::new(puzzle), |input| apply_vec_numbered(input, tfn))
+}
+
+/// Parse an input string line by line, including blank lines, to a vector with a given
+/// transform taking the 1-based line number and line
+pub fn parse_test_vec_numbered<T, F>(test: &str, tfn: F) -> Result<Vec<T>, Error>
+where
+    F: FnMut(usize, &str) -> T,
+{
+    with_input(Input::new_from_test(test), |input| {
+        apply_vec_numbered(input, tfn)
+    })
+}
+
+/// Parse an input file line by line, including blank lines, to a vector with a given
+/// fallible transform taking the 1-based line number and line
+pub fn try_parse_input_vec_numbered<T, E, F>(
+    puzzle: impl Into<Puzzle>,
+    tfn: F,
+) -> Result<Vec<T>, Error>
+where
+    F: FnMut(usize, &str) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    with_input(Input::new(puzzle), |input| {
+        try_apply_vec_numbered(input, tfn)
+    })
+}
+
+/// Parse an input string line by line, including blank lines, to a vector with a given
+/// fallible transform taking the 1-based line number and line
+pub fn try_parse_test_vec_numbered<T, E, F>(test: &str, tfn: F) -> Result<Vec<T>, Error>
+where
+    F: FnMut(usize, &str) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    with_input(Input::new_from_test(test), |input| {
+        try_apply_vec_numbered(input, tfn)
+    })
 }
 
 /// Parse an input file with a single line with a given transform
-pub fn parse_input_line<T, F>(day: usize, mut tfn: F) -> Result<T, Box<dyn Error>>
+pub fn parse_input_line<T, F>(puzzle: impl Into<Puzzle>, tfn: F) -> Result<T, Error>
 where
     F: FnMut(&str) -> T,
 {
-    Ok(tfn(Input::new(day)?
-        .lines()
-        .next()
-        .expect("No line in input")))
+    with_input(Input::new(puzzle), |input| apply_line(input, tfn))
 }
 
-/// Parse an input string to a vector with a given transform
-pub fn parse_test_vec<T, F>(test: &str, tfn: F) -> Result<Vec<T>, Box<dyn Error>>
+/// Parse an input string with a single line with a given transform
+pub fn parse_test_line<T, F>(test: &str, tfn: F) -> Result<T, Error>
 where
     F: FnMut(&str) -> T,
 {
-    Ok(test.lines().map(tfn).collect())
+    with_input(Input::new_from_test(test), |input| apply_line(input, tfn))
+}
+
+/// Parse an input file with a single line with a given fallible transform
+pub fn try_parse_input_line<T, E, F>(puzzle: impl Into<Puzzle>, tfn: F) -> Result<T, Error>
+where
+    F: FnMut(&str) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    with_input(Input::new(puzzle), |input| try_apply_line(input, tfn))
+}
+
+/// Parse an input string with a single line with a given fallible transform
+pub fn try_parse_test_line<T, E, F>(test: &str, tfn: F) -> Result<T, Error>
+where
+    F: FnMut(&str) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    with_input(Input::new_from_test(test), |input| {
+        try_apply_line(input, tfn)
+    })
+}
+
+/// Parse an input file to a grid with a given character transform
+pub fn parse_input_grid<T, F>(puzzle: impl Into<Puzzle>, mut tfn: F) -> Result<Grid<T>, Error>
+where
+    F: FnMut(char) -> T,
+{
+    with_input(Input::new(puzzle), |input| {
+        build_grid(input.numbered_lines(), |c| Ok::<_, Infallible>(tfn(c)))
+    })
 }
+
+/// Parse an input string to a grid with a given character transform
+pub fn parse_test_grid<T, F>(test: &str, mut tfn: F) -> Result<Grid<T>, Error>
+where
+    F: FnMut(char) -> T,
+{
+    with_input(Input::new_from_test(test), |input| {
+        build_grid(input.numbered_lines(), |c| Ok::<_, Infallible>(tfn(c)))
+    })
+}
+
+/// Parse an input file to a grid with a given fallible character transform
+pub fn try_parse_input_grid<T, E, F>(puzzle: impl Into<Puzzle>, tfn: F) -> Result<Grid<T>, Error>
+where
+    F: FnMut(char) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    with_input(Input::new(puzzle), |input| {
+        build_grid(input.numbered_lines(), tfn)
+    })
+}
+
+/// Parse an input string to a grid with a given fallible character transform
+pub fn try_parse_test_grid<T, E, F>(test: &str, tfn: F) -> Result<Grid<T>, Error>
+where
+    F: FnMut(char) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    with_input(Input::new_from_test(test), |input| {
+        build_grid(input.numbered_lines(), tfn)
+    })
+}
+
+/// Scan a whole input file with a given fallible scanner transform
+pub fn scan_input<T, E, F>(puzzle: impl Into<Puzzle>, tfn: F) -> Result<T, Error>
+where
+    F: FnMut(&mut Scanner) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    with_input(Input::new(puzzle), |input| apply_scanner(input, tfn))
+}
+
+/// Scan a whole input string with a given fallible scanner transform
+pub fn scan_test<T, E, F>(test: &str, tfn: F) -> Result<T, Error>
+where
+    F: FnMut(&mut Scanner) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    with_input(Input::new_from_test(test), |input| {
+        apply_scanner(input, tfn)
+    })
+}
+
+/// Parse an input file split in to blank line separated sections with given section transforms
+pub fn parse_input_sections<S>(
+    puzzle: impl Into<Puzzle>,
+    mut transforms: S,
+) -> Result<S::Output, Error>
+where
+    S: SectionTransform,
+{
+    with_input(Input::new(puzzle), |input| {
+        transforms.transform(Sections::new(input.as_str()?))
+    })
+}
+
+/// Parse an input string split in to blank line separated sections with given section transforms
+pub fn parse_test_sections<S>(test: &str, mut transforms: S) -> Result<S::Output, Error>
+where
+    S: SectionTransform,
+{
+    with_input(Input::new_from_test(test), |input| {
+        transforms.transform(Sections::new(input.as_str()?))
+    })
+}
+
+/// Parse an input file split in to blank line separated sections with given fallible section
+/// transforms
+pub fn try_parse_input_sections<S>(
+    puzzle: impl Into<Puzzle>,
+    mut transforms: S,
+) -> Result<S::Output, Error>
+where
+    S: TrySectionTransform,
+{
+    with_input(Input::new(puzzle), |input| {
+        transforms.try_transform(input.as_str()?)
+    })
+}
+
+/// Parse an input string split in to blank line separated sections with given fallible section
+/// transforms
+pub fn try_parse_test_sections<S>(test: &str, mut transforms: S) -> Result<S::Output, Error>
+where
+    S: TrySectionTransform,
+{
+    with_input(Input::new_from_test(test), |input| {
+        transforms.try_transform(input.as_str()?)
+    })
+}
+
+/// Runs a parser on a loaded input, naming the input file in any transform error
+fn with_input<T, F>(input: Result<Input, Error>, f: F) -> Result<T, Error>
+where
+    F: FnOnce(&Input) -> Result<T, Error>,
+{
+    let input = input?;
+
+    f(&input).map_err(|e| e.with_file(input.path()))
+}
+
+/// Applies a transform to a whole input
+fn apply<T, F>(input: &Input, mut tfn: F) -> Result<T, Error>
+where
+    F: FnMut(&str) -> T,
+{
+    let str = input.as_str()?;
+
+    catch_panic(|| tfn(str)).map_err(|e| input_error(str, e))
+}
+
+/// Applies a fallible transform to a whole input
+fn try_apply<T, E, F>(input: &Input, mut tfn: F) -> Result<T, Error>
+where
+    F: FnMut(&str) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    let str = input.as_str()?;
+
+    try_catch_panic(|| tfn(str)).map_err(|e| input_error(str, e))
+}
+
+/// Applies a transform to each line of an input
+fn apply_vec<T, F>(input: &Input, tfn: F) -> Result<Vec<T>, Error>
+where
+    F: FnMut(&str) -> T,
+{
+    apply_iter(input, tfn).collect()
+}
+
+/// Applies a fallible transform to each line of an input
+fn try_apply_vec<T, E, F>(input: &Input, tfn: F) -> Result<Vec<T>, Error>
+where
+    F: FnMut(&str) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    try_apply_iter(input, tfn).collect()
+}
+
+/// Returns an iterator applying a transform to each line of an input
+fn apply_iter<'a, T, F>(input: &'a Input, mut tfn: F) -> impl Iterator<Item = Result<T, Error>> + 'a
+where
+    F: FnMut(&str) -> T + 'a,
+{
+    // Keep the panic hook installed while the iterator is alive
+    let hook = HookGuard::install();
+
+    input.numbered_lines().map(move |line| {
+        let _hook = &hook;
+        let (line_no, line) = line?;
+
+        catch_panic(|| tfn(line)).map_err(|e| line_error(line_no, line, e))
+    })
+}
+
+/// Returns an iterator applying a fallible transform to each line of an input
+fn try_apply_iter<'a, T, E, F>(
+    input: &'a Input,
+    mut tfn: F,
+) -> impl Iterator<Item = Result<T, Error>> + 'a
+where
+    F: FnMut(&str) -> Result<T, E> + 'a,
+    E: Into<BoxError>,
+{
+    // Keep the panic hook installed while the iterator is alive
+    let hook = HookGuard::install();
+
+    input.numbered_lines().map(move |line| {
+        let _hook = &hook;
+        let (line_no, line) = line?;
+
+        try_catch_panic(|| tfn(line)).map_err(|e| line_error(line_no, line, e))
+    })
+}
+
+/// Folds the values of a transforming iterator, stopping at the first error
+fn fold<T, A, G>(
+    mut values: impl Iterator<Item = Result<T, Error>>,
+    init: A,
+    mut f: G,
+) -> Result<A, Error>
+where
+    G: FnMut(A, T) -> A,
+{
+    values.try_fold(init, |acc, value| Ok(f(acc, value?)))
+}
+
+/// Applies a transform to each line of an input including blank lines
+fn apply_vec_numbered<T, F>(input: &Input, mut tfn: F) -> Result<Vec<T>, Error>
+where
+    F: FnMut(usize, &str) -> T,
+{
+    let _hook = HookGuard::install();
+
+    input
+        .records()
+        .map(|record| {
+            let (line_no, line) = record?;
+
+            catch_panic(|| tfn(line_no, line)).map_err(|e| line_error(line_no, line, e))
+        })
+        .collect()
+}
+
+/// Applies a fallible transform to each line of an input including blank lines
+fn try_apply_vec_numbered<T, E, F>(input: &Input, mut tfn: F) -> Result<Vec<T>, Error>
+where
+    F: FnMut(usize, &str) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    let _hook = HookGuard::install();
+
+    input
+        .records()
+        .map(|record| {
+            let (line_no, line) = record?;
+
+            try_catch_panic(|| tfn(line_no, line)).map_err(|e| line_error(line_no, line, e))
+        })
+        .collect()
+}
+
+/// Applies a transform to the first line of an input
+fn apply_line<T, F>(input: &Input, mut tfn: F) -> Result<T, Error>
+where
+    F: FnMut(&str) -> T,
+{
+    match input.numbered_lines().next() {
+        Some(line) => {
+            let (line_no, line) = line?;
+
+            catch_panic(|| tfn(line)).map_err(|e| line_error(line_no, line, e))
+        }
+        None => Err(Error::EmptyInput),
+    }
+}
+
+/// Applies a fallible transform to the first line of an input
+fn try_apply_line<T, E, F>(input: &Input, mut tfn: F) -> Result<T, Error>
+where
+    F: FnMut(&str) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    match input.numbered_lines().next() {
+        Some(line) => {
+            let (line_no, line) = line?;
+
+            try_catch_panic(|| tfn(line)).map_err(|e| line_error(line_no, line, e))
+        }
+        None => Err(Error::EmptyInput),
+    }
+}
+
+/// Applies a fallible scanner transform to a whole input
+fn apply_scanner<T, E, F>(input: &Input, mut tfn: F) -> Result<T, Error>
+where
+    F: FnMut(&mut Scanner) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    let str = input.as_str()?;
+
+    try_catch_panic(|| tfn(&mut Scanner::new(str.as_bytes()))).map_err(|e| input_error(str, e))
+}
+
+#[cfg(test)]
+mod tests;
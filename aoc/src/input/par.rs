@@ -0,0 +1,67 @@
+use rayon::prelude::*;
+
+use crate::Error;
+
+use super::{
+    diagnostic::{try_catch_panic, HookGuard},
+    source::{split_records, Input},
+    transform::line_error,
+    BoxError,
+};
+
+/// Minimum size of a chunk of input parsed on one thread
+const MIN_CHUNK: usize = 64 * 1024;
+
+/// Applies a fallible transform to each line of an input on the rayon thread pool
+///
+/// The input is split in to chunks at line boundaries, each chunk is parsed on one
+/// thread, and the results are concatenated in input order.
+pub(super) fn try_apply_vec_par<T, E, F>(input: &Input, tfn: F) -> Result<Vec<T>, Error>
+where
+    F: Fn(&str) -> Result<T, E> + Sync,
+    E: Into<BoxError>,
+    T: Send,
+{
+    let len = input.as_bytes().len();
+    let count = (len / MIN_CHUNK).clamp(1, rayon::current_num_threads() * 4);
+    let chunks = input.chunks(count)?;
+
+    // Parse each chunk, keeping the panic hook installed throughout
+    let hook = HookGuard::install();
+
+    let results = chunks
+        .par_iter()
+        .map(|chunk| {
+            split_records(chunk)
+                .filter(|(_, line)| !line.is_empty())
+                .map(|(line_no, line)| {
+                    try_catch_panic(|| tfn(line)).map_err(|e| line_error(line_no, line, e))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Vec<_>>();
+
+    drop(hook);
+
+    // Concatenate the results
+    let mut values = Vec::with_capacity(results.iter().flatten().map(Vec::len).sum());
+
+    for (i, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(chunk_values) => values.extend(chunk_values),
+            Err(mut e) => {
+                // Line numbers are relative to the chunk
+                if let Error::Transform { line, .. } = &mut e {
+                    *line += chunks[..i]
+                        .iter()
+                        .map(|chunk| chunk.bytes().filter(|&b| b == b'\n').count())
+                        .sum::<usize>();
+                }
+
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(values)
+}
@@ -0,0 +1,289 @@
+use std::{error::Error as StdError, fmt};
+
+use super::TokenError;
+
+/// Errors raised while scanning integers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanError {
+    /// An integer was expected
+    ExpectedInteger,
+    /// The integer doesn't fit in the target type
+    Overflow,
+    /// A negative integer was found for an unsigned type
+    Negative,
+    /// A literal from the pattern was expected
+    ExpectedLiteral(String),
+    /// Input remains after the end of the pattern
+    TrailingInput,
+    /// The pattern has no more fields
+    PatternExhausted,
+}
+
+impl fmt::Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanError::ExpectedInteger => write!(f, "Expected an integer"),
+            ScanError::Overflow => write!(f, "Integer out of range"),
+            ScanError::Negative => write!(f, "Unexpected negative integer"),
+            ScanError::ExpectedLiteral(lit) => write!(f, "Expected \"{lit}\""),
+            ScanError::TrailingInput => write!(f, "Unexpected trailing input"),
+            ScanError::PatternExhausted => write!(f, "Pattern has no more fields"),
+        }
+    }
+}
+
+impl StdError for ScanError {}
+
+/// Integer types which can be scanned from bytes
+pub trait ScanInt: Sized + Copy {
+    /// True if the type is signed
+    const SIGNED: bool;
+
+    /// Parses an integer from the start of some bytes, returning it and the number of bytes used
+    fn parse_prefix(bytes: &[u8]) -> Result<(Self, usize), ScanError>;
+}
+
+macro_rules! scan_unsigned {
+    ($($t:ty),+) => {$(
+        impl ScanInt for $t {
+            const SIGNED: bool = false;
+
+            #[inline]
+            fn parse_prefix(bytes: &[u8]) -> Result<(Self, usize), ScanError> {
+                if let [b'-', b'0'..=b'9', ..] = bytes {
+                    return Err(ScanError::Negative);
+                }
+
+                let mut value: $t = 0;
+                let mut len = 0;
+
+                while let Some(&b @ b'0'..=b'9') = bytes.get(len) {
+                    value = value
+                        .checked_mul(10)
+                        .and_then(|v| v.checked_add((b - b'0') as $t))
+                        .ok_or(ScanError::Overflow)?;
+                    len += 1;
+                }
+
+                if len == 0 {
+                    Err(ScanError::ExpectedInteger)
+                } else {
+                    Ok((value, len))
+                }
+            }
+        }
+    )+};
+}
+
+macro_rules! scan_signed {
+    ($($t:ty),+) => {$(
+        impl ScanInt for $t {
+            const SIGNED: bool = true;
+
+            #[inline]
+            fn parse_prefix(bytes: &[u8]) -> Result<(Self, usize), ScanError> {
+                let negative = bytes.first() == Some(&b'-');
+                let mut len = negative as usize;
+                let start = len;
+                let mut value: $t = 0;
+
+                while let Some(&b @ b'0'..=b'9') = bytes.get(len) {
+                    let digit = (b - b'0') as $t;
+
+                    // Accumulate negative numbers downwards so MIN can be represented
+                    value = value
+                        .checked_mul(10)
+                        .and_then(|v| {
+                            if negative {
+                                v.checked_sub(digit)
+                            } else {
+                                v.checked_add(digit)
+                            }
+                        })
+                        .ok_or(ScanError::Overflow)?;
+                    len += 1;
+                }
+
+                if len == start {
+                    Err(ScanError::ExpectedInteger)
+                } else {
+                    Ok((value, len))
+                }
+            }
+        }
+    )+};
+}
+
+scan_unsigned!(u8, u16, u32, u64, u128, usize);
+scan_signed!(i8, i16, i32, i64, i128, isize);
+
+/// Scans integers from bytes, skipping any separators between them
+pub struct Scanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    /// Creates a new scanner over some bytes
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    /// Returns the next integer, or None if there are no more integers in the input
+    pub fn next_int<T: ScanInt>(&mut self) -> Option<Result<T, TokenError>> {
+        // Skip separators
+        while self.pos < self.bytes.len() && !self.starts::<T>() {
+            self.pos += 1;
+        }
+
+        if self.pos == self.bytes.len() {
+            return None;
+        }
+
+        let rest = &self.bytes[self.pos..];
+
+        Some(match T::parse_prefix(rest) {
+            Ok((value, len)) => {
+                self.pos += len;
+                Ok(value)
+            }
+            Err(e) => Err(TokenError::from_bytes(digits(rest), e)),
+        })
+    }
+
+    /// Returns an iterator over the remaining integers in the input
+    pub fn ints<T: ScanInt>(
+        &mut self,
+    ) -> impl Iterator<Item = Result<T, TokenError>> + use<'_, 'a, T> {
+        std::iter::from_fn(move || self.next_int())
+    }
+
+    /// Returns true if an integer of a type starts at the current position
+    ///
+    /// A minus sign directly after a digit separates unsigned integers, as in `1-2`.
+    /// Anywhere else it is a sign, which is an error for unsigned types.
+    #[inline]
+    fn starts<T: ScanInt>(&self) -> bool {
+        match &self.bytes[self.pos..] {
+            [b'0'..=b'9', ..] => true,
+            [b'-', b'0'..=b'9', ..] => {
+                T::SIGNED || self.pos == 0 || !self.bytes[self.pos - 1].is_ascii_digit()
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns true if the whole input has been consumed
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+}
+
+/// Matches input against a pattern of literals and `{}` integer fields
+///
+/// Used by the [`scan!`](crate::scan) macro
+pub struct Pattern<'a, 'p> {
+    input: &'a [u8],
+    pos: usize,
+    pattern: &'p str,
+}
+
+impl<'a, 'p> Pattern<'a, 'p> {
+    /// Creates a new pattern matcher
+    pub fn new<I: AsRef<[u8]> + ?Sized>(input: &'a I, pattern: &'p str) -> Self {
+        Self {
+            input: input.as_ref(),
+            pos: 0,
+            pattern,
+        }
+    }
+
+    /// Matches the literal up to the next field and parses the field
+    pub fn field<T: ScanInt>(&mut self) -> Result<T, TokenError> {
+        let (literal, rest) = self
+            .pattern
+            .split_once("{}")
+            .ok_or_else(|| self.error(0, ScanError::PatternExhausted))?;
+
+        self.literal(literal)?;
+        self.pattern = rest;
+
+        let input = &self.input[self.pos..];
+
+        match T::parse_prefix(input) {
+            Ok((value, len)) => {
+                self.pos += len;
+                Ok(value)
+            }
+            Err(e) => Err(TokenError::from_bytes(digits(input), e)),
+        }
+    }
+
+    /// Matches the remaining literal and checks all input has been consumed
+    pub fn finish(&mut self) -> Result<(), TokenError> {
+        let literal = self.pattern;
+
+        self.literal(literal)?;
+        self.pattern = "";
+
+        if self.pos == self.input.len() {
+            Ok(())
+        } else {
+            Err(self.error(self.input.len() - self.pos, ScanError::TrailingInput))
+        }
+    }
+
+    /// Matches a literal at the current position
+    fn literal(&mut self, literal: &str) -> Result<(), TokenError> {
+        if self.input[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            let len = literal.len().min(self.input.len() - self.pos);
+
+            Err(self.error(len, ScanError::ExpectedLiteral(literal.to_string())))
+        }
+    }
+
+    /// Creates an error for the input at the current position
+    fn error(&self, len: usize, err: ScanError) -> TokenError {
+        TokenError::from_bytes(&self.input[self.pos..self.pos + len], err)
+    }
+}
+
+/// Returns the leading sign and digits of some bytes, or the first byte if there are none
+fn digits(bytes: &[u8]) -> &[u8] {
+    let len = bytes
+        .iter()
+        .enumerate()
+        .position(|(i, b)| !(b.is_ascii_digit() || (i == 0 && *b == b'-')))
+        .unwrap_or(bytes.len());
+
+    &bytes[..len.max(1).min(bytes.len())]
+}
+
+/// Scans integers from input matching a pattern of literals and `{}` fields
+///
+/// The type of each field is given after the pattern. Evaluates to a
+/// `Result` containing a tuple of the fields, or a
+/// [`TokenError`](crate::input::TokenError) locating the mismatch.
+///
+/// ```
+/// let (x, y, z) = aoc::scan!("1,-2,3", "{},{},{}", u8, i16, u32).unwrap();
+///
+/// assert_eq!((x, y, z), (1, -2, 3));
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($input:expr, $pattern:expr, $($t:ty),+ $(,)?) => {{
+        let mut pattern = $crate::input::scan::Pattern::new($input, $pattern);
+
+        (|| -> ::std::result::Result<_, $crate::input::TokenError> {
+            let fields = ($(pattern.field::<$t>()?,)+);
+
+            pattern.finish()?;
+
+            Ok(fields)
+        })()
+    }};
+}
@@ -0,0 +1,278 @@
+use crate::Error;
+
+use super::{
+    diagnostic::{catch_panic, try_catch_panic, HookGuard},
+    transform::{input_error, line_error},
+    BoxError, TokenError,
+};
+
+/// Block of consecutive non-blank lines from the input
+///
+/// Iterates the lines of the block with their 1-based line numbers in the input.
+/// Carriage returns are stripped from the end of each line.
+#[derive(Debug, Clone)]
+pub struct Section<'a> {
+    text: &'a str,
+    line_no: usize,
+}
+
+impl<'a> Section<'a> {
+    /// Returns the line number of the next line in the section
+    pub fn line_no(&self) -> usize {
+        self.line_no
+    }
+
+    /// Returns an iterator over the lines of the section without line numbers
+    pub fn lines(self) -> impl Iterator<Item = &'a str> {
+        self.map(|(_, line)| line)
+    }
+}
+
+impl<'a> Iterator for Section<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.text.is_empty() {
+            return None;
+        }
+
+        let (line, rest) = split_line(self.text);
+        let line_no = self.line_no;
+
+        self.text = rest;
+        self.line_no += 1;
+
+        Some((line_no, line))
+    }
+}
+
+/// Iterator over the blank line separated sections of an input
+pub(crate) struct Sections<'a> {
+    rest: &'a str,
+    line_no: usize,
+}
+
+impl<'a> Sections<'a> {
+    /// Creates a new sections iterator
+    pub(crate) fn new(input: &'a str) -> Self {
+        Self {
+            rest: input,
+            line_no: 1,
+        }
+    }
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Skip blank lines
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+
+            let (line, rest) = split_line(self.rest);
+
+            if !line.is_empty() {
+                break;
+            }
+
+            self.rest = rest;
+            self.line_no += 1;
+        }
+
+        // Find the end of the section
+        let start = self.rest;
+        let line_no = self.line_no;
+        let mut len = 0;
+
+        while !self.rest.is_empty() {
+            let (line, rest) = split_line(self.rest);
+
+            if line.is_empty() {
+                break;
+            }
+
+            len += self.rest.len() - rest.len();
+            self.rest = rest;
+            self.line_no += 1;
+        }
+
+        Some(Section {
+            text: &start[..len],
+            line_no,
+        })
+    }
+}
+
+/// Splits the first line from a string, stripping the line ending
+fn split_line(text: &str) -> (&str, &str) {
+    let (line, rest) = match text.find('\n') {
+        Some(pos) => (&text[..pos], &text[pos + 1..]),
+        None => (text, ""),
+    };
+
+    (line.strip_suffix('\r').unwrap_or(line), rest)
+}
+
+/// Transforms applied to the sections of an input
+///
+/// Implemented for a single transform, which is applied to every section and returns
+/// a vector, and for tuples of transforms, which are applied to one section each
+/// and require the input to have exactly that many sections.
+pub trait SectionTransform {
+    type Output;
+
+    /// Applies the transform to a sequence of sections
+    fn transform<'a>(
+        &mut self,
+        sections: impl Iterator<Item = Section<'a>>,
+    ) -> Result<Self::Output, Error>;
+}
+
+impl<T, F> SectionTransform for F
+where
+    F: for<'a> FnMut(Section<'a>) -> T,
+{
+    type Output = Vec<T>;
+
+    fn transform<'a>(
+        &mut self,
+        sections: impl Iterator<Item = Section<'a>>,
+    ) -> Result<Self::Output, Error> {
+        let _hook = HookGuard::install();
+
+        sections
+            .map(|section| apply_section(&mut *self, section))
+            .collect()
+    }
+}
+
+macro_rules! tuple_section_transform {
+    ($count:expr; $($t:ident $f:ident $idx:tt),+) => {
+        impl<$($t, $f),+> SectionTransform for ($($f,)+)
+        where
+            $($f: for<'a> FnMut(Section<'a>) -> $t),+
+        {
+            type Output = ($($t,)+);
+
+            fn transform<'a>(
+                &mut self,
+                sections: impl Iterator<Item = Section<'a>>,
+            ) -> Result<Self::Output, Error> {
+                let sections = sections.collect::<Vec<_>>();
+
+                if sections.len() != $count {
+                    return Err(Error::SectionCount {
+                        expected: $count,
+                        found: sections.len(),
+                    });
+                }
+
+                let _hook = HookGuard::install();
+                let mut sections = sections.into_iter();
+
+                Ok(($(apply_section(&mut self.$idx, sections.next().unwrap())?,)+))
+            }
+        }
+    };
+}
+
+tuple_section_transform!(1; T1 F1 0);
+tuple_section_transform!(2; T1 F1 0, T2 F2 1);
+tuple_section_transform!(3; T1 F1 0, T2 F2 1, T3 F3 2);
+tuple_section_transform!(4; T1 F1 0, T2 F2 1, T3 F3 2, T4 F4 3);
+
+/// Fallible transforms applied to the sections of an input
+///
+/// As [`SectionTransform`], but each transform returns a `Result`. Errors which are
+/// [`TokenError`]s are located by line and column in the input.
+pub trait TrySectionTransform {
+    type Output;
+
+    /// Splits an input in to sections and applies the transform to them
+    fn try_transform(&mut self, input: &str) -> Result<Self::Output, Error>;
+}
+
+impl<T, E, F> TrySectionTransform for F
+where
+    F: for<'a> FnMut(Section<'a>) -> Result<T, E>,
+    E: Into<BoxError>,
+{
+    type Output = Vec<T>;
+
+    fn try_transform(&mut self, input: &str) -> Result<Self::Output, Error> {
+        let _hook = HookGuard::install();
+
+        Sections::new(input)
+            .map(|section| try_apply_section(input, &mut *self, section))
+            .collect()
+    }
+}
+
+macro_rules! tuple_try_section_transform {
+    ($count:expr; $($t:ident $e:ident $f:ident $idx:tt),+) => {
+        impl<$($t, $e, $f),+> TrySectionTransform for ($($f,)+)
+        where
+            $($f: for<'a> FnMut(Section<'a>) -> Result<$t, $e>, $e: Into<BoxError>),+
+        {
+            type Output = ($($t,)+);
+
+            fn try_transform(&mut self, input: &str) -> Result<Self::Output, Error> {
+                let sections = Sections::new(input).collect::<Vec<_>>();
+
+                if sections.len() != $count {
+                    return Err(Error::SectionCount {
+                        expected: $count,
+                        found: sections.len(),
+                    });
+                }
+
+                let _hook = HookGuard::install();
+                let mut sections = sections.into_iter();
+
+                Ok(($(try_apply_section(input, &mut self.$idx, sections.next().unwrap())?,)+))
+            }
+        }
+    };
+}
+
+tuple_try_section_transform!(1; T1 E1 F1 0);
+tuple_try_section_transform!(2; T1 E1 F1 0, T2 E2 F2 1);
+tuple_try_section_transform!(3; T1 E1 F1 0, T2 E2 F2 1, T3 E3 F3 2);
+tuple_try_section_transform!(4; T1 E1 F1 0, T2 E2 F2 1, T3 E3 F3 2, T4 E4 F4 3);
+
+/// Applies a transform to a section, converting a panic in to an error at the first
+/// line of the section
+fn apply_section<'a, T>(
+    f: impl FnOnce(Section<'a>) -> T,
+    section: Section<'a>,
+) -> Result<T, Error> {
+    let (line_no, (line, _)) = (section.line_no, split_line(section.text));
+
+    catch_panic(|| f(section)).map_err(|e| line_error(line_no, line, e))
+}
+
+/// Applies a fallible transform to a section of an input
+///
+/// Token errors are located in the input, and other errors and panics are reported at
+/// the first line of the section.
+fn try_apply_section<'a, T, E>(
+    input: &str,
+    f: impl FnOnce(Section<'a>) -> Result<T, E>,
+    section: Section<'a>,
+) -> Result<T, Error>
+where
+    E: Into<BoxError>,
+{
+    let (line_no, (line, _)) = (section.line_no, split_line(section.text));
+
+    try_catch_panic(|| f(section)).map_err(|e| {
+        if e.is::<TokenError>() {
+            input_error(input, e)
+        } else {
+            line_error(line_no, line, e)
+        }
+    })
+}
@@ -0,0 +1,358 @@
+use std::{
+    env,
+    ffi::OsString,
+    fs::File,
+    io::{self, ErrorKind, Read},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::{Error, Puzzle};
+
+#[cfg(feature = "manifest")]
+use crate::manifest::Manifest;
+
+use super::{
+    backend::{Backend, FileBackend},
+    utf8::check_utf8,
+};
+
+#[cfg(feature = "embed-input")]
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/embedded.rs"));
+}
+
+/// Environment variable overriding the directory input files are loaded from
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Environment variable giving an input file to load in place of the puzzle's input
+pub const INPUT_VAR: &str = "AOC_INPUT";
+
+/// Command line flag giving an input file to load in place of the puzzle's input
+pub const INPUT_FLAG: &str = "--input";
+
+/// Input file contents
+enum Data {
+    /// Regular file loaded by the file backend
+    File(FileBackend),
+    /// Owned buffer read from a stream or copied from a test string
+    Owned(Vec<u8>),
+    /// Data which lives for the lifetime of the program
+    Static(&'static [u8]),
+}
+
+impl AsRef<[u8]> for Data {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Data::File(file) => file.as_ref(),
+            Data::Owned(vec) => vec,
+            Data::Static(slice) => slice,
+        }
+    }
+}
+
+/// Input data
+///
+/// Loaded from a file, stdin, the binary itself or a test string
+pub struct Input {
+    data: Data,
+    path: Option<PathBuf>,
+    /// Offset of the first invalid UTF-8 sequence, found when the input is loaded
+    invalid_utf8: Option<usize>,
+}
+
+impl Input {
+    /// Opens and loads an input file for a given puzzle or day
+    ///
+    /// If a path is given with `--input <path>` on the command line or in `AOC_INPUT`
+    /// it is loaded instead, with `-` reading the input from stdin. Otherwise
+    /// `dayNN.txt` is looked for in the year directory (see
+    /// [`year_dir`](crate::puzzle::year_dir)) of the directory given by
+    /// `AOC_INPUT_DIR`, or `inputs/` and `../inputs/`.
+    ///
+    /// With the `embed-dayNN` features, enabled by a day crate's `embed-input` feature,
+    /// the inputs for those days in the default year found at build time are compiled
+    /// in to the binary and used in preference to searching for a file.
+    ///
+    /// With the `manifest` feature, a warning is printed if an input file doesn't match
+    /// the checksum recorded in the manifest next to it.
+    pub fn new(puzzle: impl Into<Puzzle>) -> Result<Self, Error> {
+        let puzzle = puzzle.into();
+
+        match input_override() {
+            Some(arg) if arg == "-" => Self::new_from_stdin(),
+            Some(arg) => Self::new_from_path(PathBuf::from(arg)),
+            None => {
+                #[cfg(feature = "embed-input")]
+                if puzzle.year == Puzzle::default_year() && puzzle.year == embedded::YEAR {
+                    if let Some(&Some(data)) = embedded::EMBEDDED.get(puzzle.day.wrapping_sub(1)) {
+                        return Self::new_from_static(
+                            data,
+                            format!("{} (embedded)", puzzle.file_name()),
+                        );
+                    }
+                }
+
+                let (file, path) = Self::open(puzzle)?;
+
+                #[cfg(feature = "manifest")]
+                {
+                    let input = Self::new_from_file(file, path.clone())?;
+
+                    input.check_manifest(puzzle.day, &path);
+
+                    Ok(input)
+                }
+
+                #[cfg(not(feature = "manifest"))]
+                Self::new_from_file(file, path)
+            }
+        }
+    }
+
+    /// Opens and loads an input file at a given path
+    pub fn new_from_path(path: PathBuf) -> Result<Self, Error> {
+        match File::open(&path) {
+            Ok(file) => Self::new_from_file(file, path),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                Err(Error::InputNotFound { paths: vec![path] })
+            }
+            Err(source) => Err(Error::Io { path, source }),
+        }
+    }
+
+    /// Reads the input from stdin
+    ///
+    /// Stdin is only read once, so the input can be loaded more than once
+    pub fn new_from_stdin() -> Result<Self, Error> {
+        static STDIN: OnceLock<Vec<u8>> = OnceLock::new();
+
+        let data = match STDIN.get() {
+            Some(data) => data,
+            None => {
+                let mut buf = Vec::new();
+
+                io::stdin()
+                    .lock()
+                    .read_to_end(&mut buf)
+                    .map_err(|source| Error::Io {
+                        path: PathBuf::from("-"),
+                        source,
+                    })?;
+
+                STDIN.get_or_init(|| buf)
+            }
+        };
+
+        Self::new_from_static(data, "<stdin>")
+    }
+
+    /// Creates an input from data which lives for the lifetime of the program
+    fn new_from_static(data: &'static [u8], name: impl Into<PathBuf>) -> Result<Self, Error> {
+        if data.is_empty() {
+            return Err(Error::EmptyInput);
+        }
+
+        Ok(Self::from_data(Data::Static(data), Some(name.into())))
+    }
+
+    /// Creates an input from a test string
+    pub fn new_from_test(test: &str) -> Result<Self, Error> {
+        if test.is_empty() {
+            return Err(Error::EmptyInput);
+        }
+
+        Ok(Self::from_data(Data::Owned(test.as_bytes().to_vec()), None))
+    }
+
+    /// Creates an input from loaded data, checking it is valid UTF-8
+    fn from_data(data: Data, path: Option<PathBuf>) -> Self {
+        let invalid_utf8 = check_utf8(data.as_ref());
+
+        Self {
+            data,
+            path,
+            invalid_utf8,
+        }
+    }
+
+    /// Returns the path the input was loaded from, if it was loaded from a file or stream
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Returns the input as a lines iterator
+    pub fn lines(&self) -> impl Iterator<Item = Result<&str, Error>> {
+        self.numbered_lines().map(|line| line.map(|(_, line)| line))
+    }
+
+    /// Returns the input as an iterator of 1-based line numbers and non-blank lines
+    pub fn numbered_lines(&self) -> impl Iterator<Item = Result<(usize, &str), Error>> {
+        self.records()
+            .filter(|record| !matches!(record, Ok((_, ""))))
+    }
+
+    /// Returns the input as an iterator of 1-based line numbers and lines, including blank lines
+    ///
+    /// Carriage returns are stripped from the end of each line. A newline at the end of
+    /// the input terminates the last line rather than starting a new blank one.
+    pub fn records(&self) -> impl Iterator<Item = Result<(usize, &str), Error>> {
+        let (text, err) = match self.as_str() {
+            Ok(text) => (text, None),
+            Err(e) => ("", Some(e)),
+        };
+
+        err.into_iter().map(Err).chain(split_records(text).map(Ok))
+    }
+
+    /// Splits the input in to at most `count` chunks, each ending at the end of a line
+    #[cfg(feature = "parallel")]
+    pub(crate) fn chunks(&self, count: usize) -> Result<Vec<&str>, Error> {
+        let text = self.as_str()?;
+        let size = text.len().div_ceil(count.max(1));
+
+        let mut chunks = Vec::with_capacity(count);
+        let mut start = 0;
+
+        while start < text.len() {
+            let end = match text
+                .as_bytes()
+                .get(start + size..)
+                .and_then(|rest| rest.iter().position(|&b| b == b'\n'))
+            {
+                Some(pos) => start + size + pos + 1,
+                None => text.len(),
+            };
+
+            chunks.push(&text[start..end]);
+            start = end;
+        }
+
+        Ok(chunks)
+    }
+
+    /// Returns the raw bytes of the input
+    pub fn as_bytes(&self) -> &[u8] {
+        self.data.as_ref()
+    }
+
+    /// Returns the input as a string slice
+    pub fn as_str(&self) -> Result<&str, Error> {
+        match self.invalid_utf8 {
+            Some(offset) => Err(Error::InvalidUtf8 { offset }),
+            // SAFETY: the data was validated as UTF-8 once when the input was loaded,
+            // and is only ever read through a shared reference afterwards. With the
+            // memory mapped backend this assumes the file isn't changed by another
+            // process while it is mapped, as memory mapping already requires
+            None => Ok(unsafe { std::str::from_utf8_unchecked(self.data.as_ref()) }),
+        }
+    }
+
+    /// Warns if the input doesn't match the checksum in the manifest next to it
+    #[cfg(feature = "manifest")]
+    fn check_manifest(&self, day: usize, path: &Path) {
+        let dir = path.parent().unwrap_or(Path::new(""));
+
+        match Manifest::load(dir) {
+            Ok(manifest) => {
+                if manifest.matches(day, self.data.as_ref()) == Some(false) {
+                    eprintln!(
+                        "Warning: {} does not match the checksum in {}",
+                        path.display(),
+                        Manifest::path(dir).display()
+                    );
+                }
+            }
+            Err(e) => eprintln!("Warning: {e}"),
+        }
+    }
+
+    /// Opens the input file for a puzzle, returning the file and the path it was opened from
+    fn open(puzzle: Puzzle) -> Result<(File, PathBuf), Error> {
+        let paths = input_dirs()
+            .iter()
+            .map(|dir| puzzle.input_dir(dir).join(puzzle.file_name()))
+            .collect::<Vec<_>>();
+
+        for path in paths.iter() {
+            match File::open(path) {
+                Ok(f) => return Ok((f, path.clone())),
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(source) => {
+                    return Err(Error::Io {
+                        path: path.clone(),
+                        source,
+                    });
+                }
+            }
+        }
+
+        Err(Error::InputNotFound { paths })
+    }
+
+    /// Loads an open file with the file backend, falling back to reading it if it is not a regular file
+    fn new_from_file(file: File, path: PathBuf) -> Result<Self, Error> {
+        let metadata = file.metadata().map_err(|source| Error::Io {
+            path: path.clone(),
+            source,
+        })?;
+
+        if !metadata.is_file() {
+            // Pipes and devices can't be memory mapped
+            return Self::new_from_reader(file, path);
+        }
+
+        if metadata.len() == 0 {
+            return Err(Error::EmptyInput);
+        }
+
+        let data = Data::File(FileBackend::load(file, path.clone())?);
+
+        Ok(Self::from_data(data, Some(path)))
+    }
+
+    /// Reads a stream in to memory
+    fn new_from_reader(mut reader: impl Read, path: PathBuf) -> Result<Self, Error> {
+        let mut buf = Vec::new();
+
+        reader.read_to_end(&mut buf).map_err(|source| Error::Io {
+            path: path.clone(),
+            source,
+        })?;
+
+        if buf.is_empty() {
+            return Err(Error::EmptyInput);
+        }
+
+        Ok(Self::from_data(Data::Owned(buf), Some(path)))
+    }
+}
+
+/// Splits text in to 1-based line numbers and lines, including blank lines
+pub(crate) fn split_records(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split_terminator('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line))
+}
+
+/// Returns the input path given on the command line or in the environment, if any
+fn input_override() -> Option<OsString> {
+    let mut args = env::args_os().skip(1);
+
+    while let Some(arg) = args.next() {
+        if arg == INPUT_FLAG {
+            return args.next();
+        }
+    }
+
+    env::var_os(INPUT_VAR)
+}
+
+/// Returns the directories input files are looked for in, in search order
+pub(crate) fn input_dirs() -> Vec<PathBuf> {
+    match env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => vec![PathBuf::from(dir)],
+        None => vec![PathBuf::from("inputs"), PathBuf::from("../inputs")],
+    }
+}
@@ -0,0 +1,420 @@
+use super::*;
+use crate::test_util::TempDir;
+
+#[test]
+fn sections_lf() {
+    let sections = parse_test_sections("a\nb\n\nc\n", |s: Section| {
+        s.map(|(n, l)| (n, l.to_string())).collect::<Vec<_>>()
+    })
+    .unwrap();
+
+    assert_eq!(
+        sections,
+        vec![
+            vec![(1, "a".to_string()), (2, "b".to_string())],
+            vec![(4, "c".to_string())]
+        ]
+    );
+}
+
+#[test]
+fn sections_crlf() {
+    let sections = parse_test_sections("\r\na\r\nb\r\n\r\n\r\nc\r\n", |s: Section| {
+        s.map(|(n, l)| (n, l.to_string())).collect::<Vec<_>>()
+    })
+    .unwrap();
+
+    assert_eq!(
+        sections,
+        vec![
+            vec![(2, "a".to_string()), (3, "b".to_string())],
+            vec![(6, "c".to_string())]
+        ]
+    );
+}
+
+#[test]
+fn sections_tuple() {
+    let (a, b) = parse_test_sections(
+        "1\n2\n\nx\n",
+        (
+            |s: Section| s.lines().map(|l| l.parse::<u8>().unwrap()).sum::<u8>(),
+            |s: Section| s.line_no(),
+        ),
+    )
+    .unwrap();
+
+    assert_eq!((a, b), (3, 4));
+
+    assert!(matches!(
+        parse_test_sections("1\n", (|_: Section| (), |_: Section| ())),
+        Err(Error::SectionCount {
+            expected: 2,
+            found: 1
+        })
+    ));
+}
+
+#[test]
+fn scanner_ints() {
+    let mut scanner = Scanner::new(b"x=12, y=-7 z:+3");
+
+    assert_eq!(scanner.next_int::<i32>().unwrap().unwrap(), 12);
+    assert_eq!(scanner.next_int::<i32>().unwrap().unwrap(), -7);
+    assert_eq!(scanner.next_int::<i32>().unwrap().unwrap(), 3);
+    assert!(scanner.next_int::<i32>().is_none());
+
+    let mut scanner = Scanner::new(b"1-2 300");
+
+    assert_eq!(
+        scanner
+            .ints::<u8>()
+            .take(2)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap(),
+        vec![1, 2]
+    );
+    assert!(scanner.next_int::<u8>().unwrap().is_err());
+
+    // Negative numbers are errors for unsigned types
+    let mut scanner = Scanner::new(b"x=-5");
+
+    assert!(scanner.next_int::<u32>().unwrap().is_err());
+}
+
+#[test]
+fn scan_pattern() {
+    assert_eq!(crate::scan!("10-20", "{}-{}", u64, u64).unwrap(), (10, 20));
+    assert_eq!(crate::scan!("<-5>", "<{}>", i8).unwrap(), (-5,));
+    assert!(crate::scan!("-5", "{}", u32).is_err());
+
+    let err = try_parse_test_vec("1,2,3\n4,x,6", |line| {
+        crate::scan!(line, "{},{},{}", u8, u8, u8)
+    })
+    .unwrap_err();
+
+    assert!(matches!(
+        err,
+        Error::Transform {
+            line: 2,
+            column: Some(3),
+            ..
+        }
+    ));
+
+    let err = try_parse_test_vec("1,2,3,", |line| crate::scan!(line, "{},{},{}", u8, u8, u8))
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        Error::Transform {
+            line: 1,
+            column: Some(6),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn lines_crlf() {
+    let lines = parse_test_vec("a\r\n\r\nb\r\n", |l| l.to_string()).unwrap();
+
+    assert_eq!(lines, vec!["a".to_string(), "b".to_string()]);
+
+    let line = parse_test_line("\r\nabc\r\n", |l| l.len()).unwrap();
+
+    assert_eq!(line, 3);
+}
+
+#[test]
+fn empty_input() {
+    assert!(matches!(
+        parse_test("", |s| s.len()),
+        Err(Error::EmptyInput)
+    ));
+}
+
+#[test]
+fn records_keep_blanks() {
+    let records = parse_test_vec_numbered("a\r\n\r\nb\n\nc\n", |n, l| (n, l.to_string())).unwrap();
+
+    assert_eq!(
+        records,
+        vec![
+            (1, "a".to_string()),
+            (2, String::new()),
+            (3, "b".to_string()),
+            (4, String::new()),
+            (5, "c".to_string())
+        ]
+    );
+
+    // Trailing blank lines are kept, the final newline is not a blank line
+    let records = parse_test_vec_numbered("a\n\n", |n, l| (n, l.len())).unwrap();
+
+    assert_eq!(records, vec![(1, 1), (2, 0)]);
+}
+
+#[test]
+fn records_error_line() {
+    let err = try_parse_test_vec_numbered("1\n\nx\n", |_, l| {
+        if l.is_empty() {
+            Ok(0)
+        } else {
+            l.parse::<u32>()
+        }
+    })
+    .unwrap_err();
+
+    assert!(matches!(err, Error::Transform { line: 3, .. }));
+}
+
+#[test]
+fn variants() {
+    let temp = TempDir::new("variants");
+    let dir = temp.path();
+
+    std::fs::create_dir_all(dir.join("day12")).unwrap();
+
+    for file in [
+        "day12.txt",
+        "day12-zed.txt",
+        "day12/bob.txt",
+        "day12/notes.md",
+        "day01.txt",
+        "day120.txt",
+    ] {
+        std::fs::write(dir.join(file), "1\n").unwrap();
+    }
+
+    let variants = variant::variants_in(dir, 12).unwrap();
+
+    assert_eq!(
+        variants
+            .iter()
+            .map(|v| (v.name.as_str(), v.path.strip_prefix(dir).unwrap()))
+            .collect::<Vec<_>>(),
+        vec![
+            (DEFAULT_VARIANT, std::path::Path::new("day12.txt")),
+            ("bob", std::path::Path::new("day12/bob.txt")),
+            ("zed", std::path::Path::new("day12-zed.txt")),
+        ]
+    );
+
+    assert!(matches!(
+        variant::variants_in(dir, 2),
+        Err(Error::InputNotFound { .. })
+    ));
+}
+
+#[test]
+fn diagnostic() {
+    let err =
+        try_parse_test_vec("1,2\n3,x5\n", |line| crate::scan!(line, "{},{}", u8, u8)).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "Expected an integer\n --> 2:3\n  |\n2 | 3,x5\n  |   ^"
+    );
+}
+
+#[test]
+fn transform_panic() {
+    let err = parse_test_vec("1\nx\n", |line| line.parse::<u8>().unwrap()).unwrap_err();
+
+    match err {
+        Error::Transform {
+            line, column, text, ..
+        } => {
+            assert_eq!((line, column, text.as_str()), (2, None, "x"));
+        }
+        _ => panic!("Unexpected error {err}"),
+    }
+
+    let err = parse_test_line("abc", |_| -> u8 { panic!("Bad line") }).unwrap_err();
+
+    assert!(err.to_string().contains("Bad line"));
+
+    // Grid cells are located by line and column
+    let err = parse_test_grid("..\n.#\n", |c| match c {
+        '.' => false,
+        _ => panic!("Bad cell"),
+    })
+    .unwrap_err();
+
+    match err {
+        Error::Transform {
+            line, column, text, ..
+        } => {
+            assert_eq!((line, column, text.as_str()), (2, Some(2), ".#"));
+        }
+        _ => panic!("Unexpected error {err}"),
+    }
+
+    // Sections are located at their first line
+    let err = parse_test_sections("1\n2\n\nx\ny\n", |section: Section| {
+        section
+            .lines()
+            .map(|line| line.parse::<u8>().unwrap())
+            .collect::<Vec<_>>()
+    })
+    .unwrap_err();
+
+    match err {
+        Error::Transform {
+            line, column, text, ..
+        } => {
+            assert_eq!((line, column, text.as_str()), (4, None, "x"));
+        }
+        _ => panic!("Unexpected error {err}"),
+    }
+
+    let err = parse_test_sections(
+        "1\n\n2\n",
+        (
+            |section: Section| section.count(),
+            |_: Section| -> u8 { panic!("Bad section") },
+        ),
+    )
+    .unwrap_err();
+
+    assert!(err.to_string().contains("Bad section"));
+}
+
+#[test]
+fn try_sections() {
+    let ints = |section: Section| {
+        section
+            .lines()
+            .map(|line| crate::scan!(line, "{}", u8).map(|(value,)| value))
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    assert_eq!(
+        try_parse_test_sections("1\n2\n\n3\n", ints).unwrap(),
+        vec![vec![1, 2], vec![3]]
+    );
+
+    // Token errors are located in the input
+    let err = try_parse_test_sections("1\n\n2\n 3\n", (ints, ints)).unwrap_err();
+
+    match err {
+        Error::Transform {
+            line, column, text, ..
+        } => {
+            assert_eq!((line, column, text.as_str()), (4, Some(1), " 3"));
+        }
+        _ => panic!("Unexpected error {err}"),
+    }
+
+    // Other errors and panics are reported at the start of the section
+    let err = try_parse_test_sections("1\n\nx\ny\n", |section: Section| {
+        section
+            .lines()
+            .map(|line| line.parse::<u8>())
+            .collect::<Result<Vec<_>, _>>()
+    })
+    .unwrap_err();
+
+    assert!(matches!(
+        err,
+        Error::Transform {
+            line: 3,
+            column: None,
+            ..
+        }
+    ));
+
+    let err = try_parse_test_sections("1\n", |_: Section| -> Result<u8, TokenError> {
+        panic!("Bad section")
+    })
+    .unwrap_err();
+
+    assert!(err.to_string().contains("Bad section"));
+}
+
+#[test]
+fn utf8_check() {
+    assert_eq!(utf8::check_utf8(b"0123456789abcdef\n"), None);
+    assert_eq!(
+        utf8::check_utf8("0123456789\u{e9}\u{1f600}\n".as_bytes()),
+        None
+    );
+    assert_eq!(utf8::check_utf8(b"0123456789\xff\n"), Some(10));
+    assert_eq!(utf8::check_utf8(b"0123456789\xc3\xa9ab\xc3"), Some(14));
+
+    // Invalid data is reported when the input is read
+    let dir = TempDir::new("utf8");
+    std::fs::write(dir.path().join("input.txt"), b"1\n2\n\x803\n").unwrap();
+
+    let input = Input::new_from_path(dir.path().join("input.txt")).unwrap();
+
+    assert!(matches!(
+        input.as_str(),
+        Err(Error::InvalidUtf8 { offset: 4 })
+    ));
+    assert!(matches!(
+        input.lines().next(),
+        Some(Err(Error::InvalidUtf8 { offset: 4 }))
+    ));
+}
+
+#[test]
+fn fold_and_iter() {
+    let sum = fold_test(
+        "1\n2\n\n3\n",
+        |line| line.parse::<u32>().unwrap(),
+        0,
+        |a, v| a + v,
+    );
+
+    assert_eq!(sum.unwrap(), 6);
+
+    let input = Input::new_from_test("1\nx\n3").unwrap();
+    let mut values = try_parse_input_iter(&input, |line| line.parse::<u32>());
+
+    assert_eq!(values.next().unwrap().unwrap(), 1);
+
+    match values.next().unwrap().unwrap_err() {
+        Error::Transform { line, text, .. } => assert_eq!((line, text.as_str()), (2, "x")),
+        err => panic!("Unexpected error {err}"),
+    }
+
+    assert_eq!(values.next().unwrap().unwrap(), 3);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn vec_par() {
+    // Large enough to be split in to several chunks
+    let input = (0..200_000)
+        .map(|i| {
+            if i % 1000 == 0 {
+                String::new()
+            } else {
+                i.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\r\n");
+
+    let values = parse_test_vec_par(&input, |line| line.parse::<u32>().unwrap()).unwrap();
+
+    assert_eq!(
+        values,
+        parse_test_vec(&input, |line| line.parse::<u32>().unwrap()).unwrap()
+    );
+
+    let input = input.replace("\r\n150001\r\n", "\r\n150x01\r\n");
+
+    let err = try_parse_test_vec_par(&input, |line| crate::scan!(line, "{}", u32)).unwrap_err();
+
+    match err {
+        Error::Transform {
+            line, column, text, ..
+        } => {
+            assert_eq!((line, column, text.as_str()), (150_002, Some(4), "150x01"));
+        }
+        _ => panic!("Unexpected error {err}"),
+    }
+}
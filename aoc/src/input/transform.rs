@@ -0,0 +1,128 @@
+use std::{error::Error as StdError, fmt};
+
+use crate::Error;
+
+/// Boxed error returned from a fallible transform
+pub type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// Transform error which identifies the offending token in the text being transformed
+///
+/// The token must be a sub-slice of the string passed to the transform. Only its
+/// address and length are kept, so the error does not borrow from the input.
+#[derive(Debug)]
+pub struct TokenError {
+    addr: usize,
+    len: usize,
+    source: BoxError,
+}
+
+impl TokenError {
+    /// Creates a new token error for a token slice
+    pub fn new(token: &str, source: impl Into<BoxError>) -> Self {
+        Self {
+            addr: token.as_ptr() as usize,
+            len: token.len(),
+            source: source.into(),
+        }
+    }
+
+    /// Creates a new token error for a token byte slice
+    pub(crate) fn from_bytes(token: &[u8], source: impl Into<BoxError>) -> Self {
+        Self {
+            addr: token.as_ptr() as usize,
+            len: token.len(),
+            source: source.into(),
+        }
+    }
+
+    /// Returns the byte offset and length of the token within a string, if it lies within it
+    fn locate(&self, text: &str) -> Option<(usize, usize)> {
+        let start = text.as_ptr() as usize;
+
+        if self.addr >= start && self.addr + self.len <= start + text.len() {
+            Some((self.addr - start, self.len))
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for TokenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+impl StdError for TokenError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source.source()
+    }
+}
+
+/// Builds the error for a failed line transform
+pub(crate) fn line_error(line_no: usize, line: &str, err: impl Into<BoxError>) -> Error {
+    let source = err.into();
+
+    let (location, source) = match source.downcast::<TokenError>() {
+        Ok(token) => (token.locate(line), token.source),
+        Err(source) => (None, source),
+    };
+
+    Error::Transform {
+        file: None,
+        line: line_no,
+        column: location.map(|(offset, _)| char_column(line, offset)),
+        width: location.map_or(1, |(offset, len)| char_width(line, offset, len)),
+        text: line.to_string(),
+        source,
+    }
+}
+
+/// Builds the error for a failed whole input transform
+pub(crate) fn input_error(input: &str, err: impl Into<BoxError>) -> Error {
+    let source = err.into();
+
+    match source.downcast::<TokenError>() {
+        Ok(token) => match token.locate(input) {
+            Some((offset, len)) => {
+                // Find the line containing the token
+                let bytes = input.as_bytes();
+                let line_start = bytes[..offset]
+                    .iter()
+                    .rposition(|&b| b == b'\n')
+                    .map(|p| p + 1)
+                    .unwrap_or(0);
+                let line_end = bytes[offset..]
+                    .iter()
+                    .position(|&b| b == b'\n')
+                    .map(|p| p + offset)
+                    .unwrap_or(input.len());
+                let line = &input[line_start..line_end];
+
+                Error::Transform {
+                    file: None,
+                    line: bytes[..line_start].iter().filter(|&&b| b == b'\n').count() + 1,
+                    column: Some(char_column(&input[line_start..], offset - line_start)),
+                    width: char_width(input, offset, len),
+                    text: line.strip_suffix('\r').unwrap_or(line).to_string(),
+                    source: token.source,
+                }
+            }
+            None => Error::InputTransform {
+                source: token.source,
+            },
+        },
+        Err(source) => Error::InputTransform { source },
+    }
+}
+
+/// Returns the 1-based character column of a byte offset in a line
+fn char_column(line: &str, offset: usize) -> usize {
+    line.char_indices().take_while(|(i, _)| *i < offset).count() + 1
+}
+
+/// Returns the number of characters in a token at a byte offset and length in some text
+fn char_width(text: &str, offset: usize, len: usize) -> usize {
+    text.get(offset..offset + len)
+        .map_or(len, |token| token.chars().count())
+}
@@ -0,0 +1,23 @@
+use std::mem::size_of;
+
+/// Returns the offset of the first invalid UTF-8 sequence in some data, if there is one
+///
+/// Puzzle inputs are almost always ASCII, so the data is checked a word at a time until
+/// a byte with the high bit set is found, and only the rest is fully validated.
+pub(super) fn check_utf8(data: &[u8]) -> Option<usize> {
+    const WORD: usize = size_of::<usize>();
+    const HIGH_BITS: usize = usize::from_ne_bytes([0x80; WORD]);
+
+    // Skip ASCII words
+    let ascii = data
+        .chunks_exact(WORD)
+        .take_while(|word| usize::from_ne_bytes((*word).try_into().unwrap()) & HIGH_BITS == 0)
+        .count()
+        * WORD;
+
+    // Validate the rest, which starts on a character boundary after the ASCII prefix
+    match std::str::from_utf8(&data[ascii..]) {
+        Ok(_) => None,
+        Err(e) => Some(ascii + e.valid_up_to()),
+    }
+}
@@ -0,0 +1,108 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::{Error, Puzzle};
+
+use super::source::input_dirs;
+
+/// Name given to the `dayNN.txt` input
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// Named input file for a day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    /// Name of the input
+    pub name: String,
+    /// Path of the input file
+    pub path: PathBuf,
+}
+
+/// Finds all of the input files for a puzzle or day, sorted by name with the default first
+///
+/// Discovers `dayNN.txt` (named `default`), `dayNN-<name>.txt` and `dayNN/<name>.txt`
+/// in the year directory of the first input directory which exists.
+pub fn find_variants(puzzle: impl Into<Puzzle>) -> Result<Vec<Variant>, Error> {
+    let puzzle = puzzle.into();
+    let dirs = input_dirs();
+
+    let Some(dir) = dirs.iter().find(|dir| dir.is_dir()) else {
+        return Err(Error::InputNotFound { paths: dirs });
+    };
+
+    variants_in(&puzzle.input_dir(dir), puzzle.day)
+}
+
+/// Finds all of the input files for a day in an input directory
+pub(super) fn variants_in(dir: &Path, day: usize) -> Result<Vec<Variant>, Error> {
+    let base = format!("day{day:02}");
+    let mut variants = Vec::new();
+
+    // Look for dayNN.txt and dayNN-<name>.txt
+    for (name, path) in txt_files(dir)? {
+        if name == base {
+            variants.push(Variant {
+                name: DEFAULT_VARIANT.to_string(),
+                path,
+            });
+        } else if let Some(name) = name.strip_prefix(&format!("{base}-")) {
+            variants.push(Variant {
+                name: name.to_string(),
+                path,
+            });
+        }
+    }
+
+    // Look for dayNN/<name>.txt
+    let day_dir = dir.join(&base);
+
+    if day_dir.is_dir() {
+        for (name, path) in txt_files(&day_dir)? {
+            variants.push(Variant { name, path });
+        }
+    }
+
+    if variants.is_empty() {
+        return Err(Error::InputNotFound {
+            paths: vec![dir.join(format!("{base}.txt")), day_dir],
+        });
+    }
+
+    variants.sort_by(|a, b| {
+        (a.name != DEFAULT_VARIANT)
+            .cmp(&(b.name != DEFAULT_VARIANT))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(variants)
+}
+
+/// Returns the stems and paths of the .txt files in a directory
+fn txt_files(dir: &Path) -> Result<Vec<(String, PathBuf)>, Error> {
+    let io_error = |source| Error::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(io_error(e)),
+    };
+
+    let mut files = Vec::new();
+
+    for entry in entries {
+        let path = entry.map_err(io_error)?.path();
+
+        if path.extension().is_some_and(|ext| ext == "txt") && path.is_file() {
+            if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                files.push((stem.to_string(), path.clone()));
+            }
+        }
+    }
+
+    Ok(files)
+}
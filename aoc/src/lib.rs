@@ -1,4 +1,28 @@
+// Allows code generated by aoc-derive to name this crate from within it
+extern crate self as aoc;
+
+mod error;
+pub use error::Error;
+
+#[cfg(feature = "fetch")]
+pub mod fetch;
+
 #[cfg(feature = "gif")]
 pub mod gif;
 
+pub mod grid;
+
 pub mod input;
+
+#[cfg(feature = "manifest")]
+pub mod manifest;
+
+pub mod parse;
+
+pub mod puzzle;
+pub use puzzle::Puzzle;
+
+pub mod runner;
+
+#[cfg(test)]
+mod test_util;
@@ -0,0 +1,348 @@
+use std::{
+    env,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+#[cfg(feature = "fetch")]
+use aoc::fetch::{load_token, Fetcher, BASE_URL_VAR};
+use aoc::{
+    input::{find_variants, INPUT_DIR_VAR},
+    manifest::Manifest,
+    puzzle::year_dir,
+    runner::{build_day, run_day},
+    Puzzle,
+};
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    let result = match args.split_first() {
+        #[cfg(feature = "fetch")]
+        Some((cmd, rest)) if cmd == "fetch" => fetch(rest),
+        Some((cmd, rest)) if cmd == "record" => record(rest),
+        Some((cmd, rest)) if cmd == "verify" => verify(rest),
+        Some((cmd, rest)) if cmd == "run" => run(rest),
+        _ => Err(usage()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Returns the usage message
+fn usage() -> Box<dyn Error> {
+    let mut usage = String::from("Usage:\n");
+
+    #[cfg(feature = "fetch")]
+    usage.push_str("  aoc fetch [--base-url <url>] [--year <year>] <day>...\n");
+
+    usage.push_str("  aoc record [--year <year>] <day>...\n");
+    usage.push_str("  aoc verify [--year <year>] [<day>...]\n");
+    usage.push_str("  aoc run [--year <year>] <day>...");
+
+    usage.into()
+}
+
+/// Downloads the inputs for one or more days
+#[cfg(feature = "fetch")]
+fn fetch(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut base_url = env::var(BASE_URL_VAR).ok();
+    let mut rest = Vec::new();
+
+    // Parse arguments
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if arg == "--base-url" {
+            base_url = Some(args.next().ok_or_else(usage)?.clone());
+        } else {
+            rest.push(arg.clone());
+        }
+    }
+
+    let (_, puzzles) = parse_puzzles(&rest)?;
+
+    if puzzles.is_empty() {
+        return Err(usage());
+    }
+
+    // Create the fetcher
+    let mut fetcher = Fetcher::new(&load_token(&PathBuf::from("token.txt"))?)?;
+
+    if let Some(base_url) = base_url {
+        fetcher = fetcher.base_url(&base_url);
+    }
+
+    let base = input_dir();
+
+    // Download each day, recording the checksums of new inputs
+    let mut failed = false;
+
+    for puzzle in puzzles {
+        let day = puzzle.day;
+
+        match fetcher.fetch(puzzle, &base) {
+            Ok(path) => {
+                let input = match read(&path) {
+                    Ok(input) => input,
+                    Err(e) => {
+                        eprintln!("Day {day}: {e}");
+                        failed = true;
+                        continue;
+                    }
+                };
+
+                let dir = puzzle.input_dir(&base);
+                let mut manifest = Manifest::load(&dir)?;
+
+                manifest.set_input(day, &input);
+                manifest.save(&dir)?;
+
+                println!("Day {day}: saved to {}", path.display());
+            }
+            Err(e) => {
+                eprintln!("Day {day}: {e}");
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        Err("Some inputs were not downloaded".into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Runs one or more days and records their input checksums and answers in the manifest
+fn record(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (year, puzzles) = parse_puzzles(args)?;
+
+    if puzzles.is_empty() {
+        return Err(usage());
+    }
+
+    let dir = year_dir(&input_dir(), year);
+    let mut manifest = Manifest::load(&dir)?;
+    let mut failed = false;
+
+    for puzzle in puzzles {
+        let day = puzzle.day;
+        let path = dir.join(puzzle.file_name());
+
+        let input = match read(&path) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("Day {day}: {e}");
+                failed = true;
+                continue;
+            }
+        };
+
+        let answers = run_day(puzzle, &build_day(day)?, Some(&path))?.answers;
+
+        println!(
+            "Day {day}: part 1 {}, part 2 {}",
+            answers[0].as_deref().unwrap_or("-"),
+            answers[1].as_deref().unwrap_or("-")
+        );
+
+        manifest.set_input(day, &input);
+        manifest.set_answers(day, answers);
+    }
+
+    manifest.save(&dir)?;
+
+    if failed {
+        Err("Some days were not recorded".into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Runs days in the manifest and compares their answers and input checksums with it
+fn verify(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (year, mut puzzles) = parse_puzzles(args)?;
+
+    let dir = year_dir(&input_dir(), year);
+    let manifest = Manifest::load(&dir)?;
+
+    if puzzles.is_empty() {
+        puzzles = manifest
+            .iter()
+            .map(|(day, _)| Puzzle::new(year, day))
+            .collect();
+    }
+
+    let mut failed = false;
+
+    for puzzle in puzzles {
+        let day = puzzle.day;
+
+        let Some(entry) = manifest.get(day) else {
+            println!("Day {day}: not in manifest");
+            failed = true;
+            continue;
+        };
+
+        // Check the input
+        let path = dir.join(puzzle.file_name());
+
+        let input = match read(&path) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("Day {day}: {e}");
+                failed = true;
+                continue;
+            }
+        };
+
+        if manifest.matches(day, &input) != Some(true) {
+            println!(
+                "Day {day}: {} does not match the manifest checksum",
+                path.display()
+            );
+            failed = true;
+            continue;
+        }
+
+        // Check the answers
+        let answers = run_day(puzzle, &build_day(day)?, Some(&path))?.answers;
+
+        for (part, (expected, got)) in entry.answers.iter().zip(answers.iter()).enumerate() {
+            let expected = expected.as_deref().unwrap_or("-");
+            let got = got.as_deref().unwrap_or("-");
+
+            if expected == got {
+                println!("Day {day} part {}: {got} OK", part + 1);
+            } else {
+                println!(
+                    "Day {day} part {}: expected {expected}, got {got}",
+                    part + 1
+                );
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        Err("Verification failed".into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Runs every input variant for one or more days and tabulates the answers and timings
+fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (_, puzzles) = parse_puzzles(args)?;
+
+    if puzzles.is_empty() {
+        return Err(usage());
+    }
+
+    for puzzle in puzzles {
+        let day = puzzle.day;
+        let binary = build_day(day)?;
+
+        // Run each variant
+        let rows = find_variants(puzzle)?
+            .into_iter()
+            .map(|variant| {
+                let [part1, part2, time] = match run_day(puzzle, &binary, Some(&variant.path)) {
+                    Ok(run) => {
+                        let [part1, part2] = run.answers.map(|a| a.unwrap_or_else(|| "-".into()));
+
+                        [
+                            part1,
+                            part2,
+                            format!("{:.3}ms", run.elapsed.as_secs_f64() * 1000.0),
+                        ]
+                    }
+                    Err(e) => {
+                        eprintln!("{}: {e}", variant.path.display());
+                        ["error", "error", "-"].map(String::from)
+                    }
+                };
+
+                [variant.name, part1, part2, time]
+            })
+            .collect::<Vec<_>>();
+
+        // Print the table
+        let header = ["Input", "Part 1", "Part 2", "Time"].map(String::from);
+
+        let widths = (0..4)
+            .map(|col| {
+                rows.iter()
+                    .chain([&header])
+                    .map(|row| row[col].len())
+                    .max()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        println!("{puzzle}");
+
+        for row in [&header].into_iter().chain(rows.iter()) {
+            println!(
+                "  {:<w0$}  {:>w1$}  {:>w2$}  {:>w3$}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+                w3 = widths[3]
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses day number arguments and an optional `--year <year>`, returning the year and puzzles
+fn parse_puzzles(args: &[String]) -> Result<(usize, Vec<Puzzle>), Box<dyn Error>> {
+    let mut year = Puzzle::default_year();
+    let mut days = Vec::new();
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if arg == "--year" {
+            year = args
+                .next()
+                .and_then(|year| year.parse().ok())
+                .ok_or_else(usage)?;
+        } else {
+            match arg.parse::<usize>() {
+                Ok(day @ 1..=25) => days.push(day),
+                _ => return Err(usage()),
+            }
+        }
+    }
+
+    let puzzles = days.into_iter().map(|day| Puzzle::new(year, day)).collect();
+
+    Ok((year, puzzles))
+}
+
+/// Returns the input directory
+fn input_dir() -> PathBuf {
+    env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("inputs"))
+}
+
+/// Reads a file
+fn read(path: &Path) -> Result<Vec<u8>, Box<dyn Error>> {
+    fs::read(path).map_err(|e| format!("Error reading {}: {e}", path.display()).into())
+}
@@ -0,0 +1,170 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::Error;
+
+/// Name of the manifest file in the input directory
+pub const MANIFEST_FILE: &str = "manifest";
+
+/// Placeholder for an answer which hasn't been recorded
+const NO_ANSWER: &str = "-";
+
+/// Manifest entry for a day's input
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Entry {
+    /// SHA-256 of the input file as lower case hex
+    pub sha256: String,
+    /// Accepted answers for each part
+    pub answers: [Option<String>; 2],
+}
+
+/// Checksums and accepted answers for the input files in an input directory
+///
+/// Stored as one line per day: `dayNN <sha256> <part 1> <part 2>`, with `-` for an
+/// answer which hasn't been recorded. Blank lines and lines starting `#` are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifest {
+    entries: BTreeMap<usize, Entry>,
+}
+
+impl Manifest {
+    /// Loads the manifest from an input directory, returning an empty manifest if there isn't one
+    pub fn load(dir: &Path) -> Result<Self, Error> {
+        let path = Self::path(dir);
+
+        match fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text).map_err(|(line, message)| Error::Manifest {
+                path,
+                line,
+                message,
+            }),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(source) => Err(Error::Io { path, source }),
+        }
+    }
+
+    /// Saves the manifest to an input directory
+    pub fn save(&self, dir: &Path) -> Result<(), Error> {
+        let path = Self::path(dir);
+
+        fs::write(&path, self.to_string()).map_err(|source| Error::Io { path, source })
+    }
+
+    /// Returns the path of the manifest in an input directory
+    pub fn path(dir: &Path) -> PathBuf {
+        dir.join(MANIFEST_FILE)
+    }
+
+    /// Parses manifest text, returning the 1-based line number and message on error
+    pub fn parse(text: &str) -> Result<Self, (usize, String)> {
+        let mut entries = BTreeMap::new();
+
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let err = |message: &str| (idx + 1, message.to_string());
+
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+
+            let [day, sha256, part1, part2] = fields[..] else {
+                return Err(err("Expected day, checksum and two answers"));
+            };
+
+            let day = day
+                .strip_prefix("day")
+                .and_then(|day| day.parse::<usize>().ok())
+                .ok_or_else(|| err("Invalid day"))?;
+
+            if sha256.len() != 64 || !sha256.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(err("Invalid SHA-256 checksum"));
+            }
+
+            let answer = |answer: &str| (answer != NO_ANSWER).then(|| answer.to_string());
+
+            let entry = Entry {
+                sha256: sha256.to_ascii_lowercase(),
+                answers: [answer(part1), answer(part2)],
+            };
+
+            if entries.insert(day, entry).is_some() {
+                return Err(err("Duplicate day"));
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Returns the entry for a day
+    pub fn get(&self, day: usize) -> Option<&Entry> {
+        self.entries.get(&day)
+    }
+
+    /// Returns an iterator over the days and entries in day order
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &Entry)> {
+        self.entries.iter().map(|(day, entry)| (*day, entry))
+    }
+
+    /// Records the checksum of a day's input, clearing the answers if it has changed
+    pub fn set_input(&mut self, day: usize, input: &[u8]) {
+        let sha256 = sha256_hex(input);
+        let entry = self.entries.entry(day).or_default();
+
+        if entry.sha256 != sha256 {
+            *entry = Entry {
+                sha256,
+                answers: Default::default(),
+            };
+        }
+    }
+
+    /// Records the accepted answers for a day
+    pub fn set_answers(&mut self, day: usize, answers: [Option<String>; 2]) {
+        self.entries.entry(day).or_default().answers = answers;
+    }
+
+    /// Returns true if a day's input matches the recorded checksum, or None if there is no entry
+    pub fn matches(&self, day: usize, input: &[u8]) -> Option<bool> {
+        self.get(day).map(|entry| entry.sha256 == sha256_hex(input))
+    }
+}
+
+impl fmt::Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# day sha256 part1 part2")?;
+
+        for (day, entry) in &self.entries {
+            let answer = |part: usize| entry.answers[part].as_deref().unwrap_or(NO_ANSWER);
+
+            writeln!(
+                f,
+                "day{day:02} {} {} {}",
+                entry.sha256,
+                answer(0),
+                answer(1)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the SHA-256 of some data as lower case hex
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
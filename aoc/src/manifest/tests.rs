@@ -0,0 +1,67 @@
+use super::*;
+
+const HASH: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+#[test]
+fn sha256() {
+    assert_eq!(sha256_hex(b"hello"), HASH);
+}
+
+#[test]
+fn round_trip() {
+    let text = format!("# comment\n\nday01 {HASH} 1092 6616\nday12 {HASH} 550 -\n");
+
+    let manifest = Manifest::parse(&text).unwrap();
+
+    assert_eq!(
+        manifest.get(12),
+        Some(&Entry {
+            sha256: HASH.to_string(),
+            answers: [Some("550".to_string()), None],
+        })
+    );
+    assert_eq!(manifest.get(2), None);
+
+    assert_eq!(Manifest::parse(&manifest.to_string()).unwrap(), manifest);
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(
+        Manifest::parse(&format!("day01 {HASH} 1\n")).unwrap_err().0,
+        1
+    );
+    assert_eq!(
+        Manifest::parse(&format!("\nxx01 {HASH} 1 2\n"))
+            .unwrap_err()
+            .0,
+        2
+    );
+    assert_eq!(Manifest::parse("day01 abc 1 2\n").unwrap_err().0, 1);
+    assert_eq!(
+        Manifest::parse(&format!("day01 {HASH} 1 2\nday1 {HASH} 1 2\n"))
+            .unwrap_err()
+            .0,
+        2
+    );
+}
+
+#[test]
+fn changed_input() {
+    let mut manifest = Manifest::default();
+
+    manifest.set_input(1, b"hello");
+    manifest.set_answers(1, [Some("1".to_string()), Some("2".to_string())]);
+
+    assert_eq!(manifest.matches(1, b"hello"), Some(true));
+    assert_eq!(manifest.matches(1, b"hell"), Some(false));
+    assert_eq!(manifest.matches(2, b"hello"), None);
+
+    // Same input keeps the answers
+    manifest.set_input(1, b"hello");
+    assert!(manifest.get(1).unwrap().answers[0].is_some());
+
+    // New input clears them
+    manifest.set_input(1, b"hell");
+    assert_eq!(manifest.get(1).unwrap().answers, [None, None]);
+}
@@ -0,0 +1,442 @@
+//! Parser combinators for structured line formats
+//!
+//! Parsers are functions taking a `&str` and returning the parsed value and the rest of
+//! the input. Values borrow from the input where possible. Sequences of parsers are
+//! written as tuples, and [`parse_all`] runs a parser over a whole string, returning a
+//! [`TokenError`] locating any failure for the input diagnostics.
+//!
+//! Repeating and optional parsers stop at the first value which fails to parse, unless
+//! it failed after consuming some input, in which case the error is returned.
+//!
+//! ```
+//! use aoc::parse::{bracketed, comma_list, int, parse_all, tag, ws};
+//!
+//! let list = bracketed("(", comma_list(int::<u8>()), ")");
+//! let (name, _, _, values) = parse_all((tag("a"), tag(":"), ws, list), "a: (1,2,3)").unwrap();
+//!
+//! assert_eq!((name, values), ("a", vec![1, 2, 3]));
+//! ```
+//!
+//! With the `derive` feature, `#[derive(AocParse)]` implements [`Parse`] and `FromStr`
+//! for a type from a format string such as `#[aoc(format = "{x},{y}")]`.
+
+use std::{error::Error as StdError, fmt};
+
+#[cfg(feature = "derive")]
+pub use aoc_derive::AocParse;
+
+use crate::input::{
+    scan::{ScanError, ScanInt},
+    TokenError,
+};
+
+/// Result of a parser: the value and the remaining input, or an error
+pub type PResult<'a, T> = Result<(T, &'a str), ParseError<'a>>;
+
+/// What a parser expected to find
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expected {
+    /// An integer
+    Integer,
+    /// An integer in range of the target type
+    InRange,
+    /// A literal tag
+    Tag(&'static str),
+    /// One of a set of characters
+    OneOf(&'static str),
+    /// One or more whitespace characters
+    Whitespace,
+    /// One or more alphanumeric characters
+    Word,
+    /// The end of the input
+    End,
+    /// One of the variants of a derived enum
+    Variant(&'static str),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Integer => write!(f, "Expected an integer"),
+            Expected::InRange => write!(f, "Integer out of range"),
+            Expected::Tag(tag) => write!(f, "Expected \"{tag}\""),
+            Expected::OneOf(chars) => write!(f, "Expected one of \"{chars}\""),
+            Expected::Whitespace => write!(f, "Expected whitespace"),
+            Expected::Word => write!(f, "Expected a word"),
+            Expected::End => write!(f, "Unexpected trailing input"),
+            Expected::Variant(name) => write!(f, "Expected one of the {name} formats"),
+        }
+    }
+}
+
+impl StdError for Expected {}
+
+/// Parse error identifying the offending token in the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError<'a> {
+    /// The offending token, a sub-slice of the input
+    pub token: &'a str,
+    /// What was expected
+    pub expected: Expected,
+}
+
+impl<'a> ParseError<'a> {
+    /// Creates an error for the next character of some input
+    pub fn at(input: &'a str, expected: Expected) -> Self {
+        let len = input.chars().next().map_or(0, char::len_utf8);
+
+        Self {
+            token: &input[..len],
+            expected,
+        }
+    }
+}
+
+impl fmt::Display for ParseError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.expected.fmt(f)
+    }
+}
+
+impl From<ParseError<'_>> for TokenError {
+    fn from(err: ParseError<'_>) -> Self {
+        TokenError::new(err.token, err.expected)
+    }
+}
+
+/// A parser producing a value from the start of some input
+pub trait Parser<'a> {
+    type Output;
+
+    /// Parses the start of some input, returning the value and the rest of the input
+    fn parse(&mut self, input: &'a str) -> PResult<'a, Self::Output>;
+}
+
+impl<'a, T, F> Parser<'a> for F
+where
+    F: FnMut(&'a str) -> PResult<'a, T>,
+{
+    type Output = T;
+
+    fn parse(&mut self, input: &'a str) -> PResult<'a, T> {
+        self(input)
+    }
+}
+
+macro_rules! tuple_parser {
+    ($($p:ident $v:ident),+) => {
+        impl<'a, $($p),+> Parser<'a> for ($($p,)+)
+        where
+            $($p: Parser<'a>),+
+        {
+            type Output = ($($p::Output,)+);
+
+            #[allow(non_snake_case)]
+            fn parse(&mut self, input: &'a str) -> PResult<'a, Self::Output> {
+                let ($($p,)+) = self;
+
+                $(let ($v, input) = $p.parse(input)?;)+
+
+                Ok((($($v,)+), input))
+            }
+        }
+    };
+}
+
+tuple_parser!(P1 v1, P2 v2);
+tuple_parser!(P1 v1, P2 v2, P3 v3);
+tuple_parser!(P1 v1, P2 v2, P3 v3, P4 v4);
+tuple_parser!(P1 v1, P2 v2, P3 v3, P4 v4, P5 v5);
+tuple_parser!(P1 v1, P2 v2, P3 v3, P4 v4, P5 v5, P6 v6);
+
+/// Types which can be parsed from the start of some input
+///
+/// Implemented for integers, and by `#[derive(AocParse)]`
+pub trait Parse: Sized {
+    /// Parses a value from the start of some input, returning the value and the rest of the input
+    fn parse_from(input: &str) -> PResult<'_, Self>;
+}
+
+macro_rules! parse_int {
+    ($($t:ty),+) => {$(
+        impl Parse for $t {
+            fn parse_from(input: &str) -> PResult<'_, Self> {
+                int()(input)
+            }
+        }
+    )+};
+}
+
+parse_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Runs a parser over a whole string, failing if any input is left over
+pub fn parse_all<'a, P>(mut parser: P, input: &'a str) -> Result<P::Output, TokenError>
+where
+    P: Parser<'a>,
+{
+    let (value, rest) = parser.parse(input)?;
+
+    if rest.is_empty() {
+        Ok(value)
+    } else {
+        Err(ParseError {
+            token: rest,
+            expected: Expected::End,
+        }
+        .into())
+    }
+}
+
+/// Parses an integer
+pub fn int<'a, T: ScanInt>() -> impl FnMut(&'a str) -> PResult<'a, T> {
+    |input| match T::parse_prefix(input.as_bytes()) {
+        Ok((value, len)) => Ok((value, &input[len..])),
+        Err(ScanError::Overflow) => {
+            let len = input
+                .bytes()
+                .enumerate()
+                .position(|(i, b)| !(b.is_ascii_digit() || (i == 0 && b == b'-')))
+                .unwrap_or(input.len());
+
+            Err(ParseError {
+                token: &input[..len],
+                expected: Expected::InRange,
+            })
+        }
+        Err(_) => Err(ParseError::at(input, Expected::Integer)),
+    }
+}
+
+/// Parses a literal tag
+pub fn tag<'a>(tag: &'static str) -> impl FnMut(&'a str) -> PResult<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(tag) {
+        Some(rest) => Ok((&input[..tag.len()], rest)),
+        None => Err(ParseError::at(input, Expected::Tag(tag))),
+    }
+}
+
+/// Parses one of a set of characters
+pub fn one_of<'a>(chars: &'static str) -> impl FnMut(&'a str) -> PResult<'a, char> {
+    move |input: &'a str| match input.chars().next() {
+        Some(c) if chars.contains(c) => Ok((c, &input[c.len_utf8()..])),
+        _ => Err(ParseError::at(input, Expected::OneOf(chars))),
+    }
+}
+
+/// Parses one or more whitespace characters
+pub fn ws(input: &str) -> PResult<'_, &str> {
+    take_while1(input, char::is_whitespace, Expected::Whitespace)
+}
+
+/// Parses one or more alphanumeric characters
+pub fn word(input: &str) -> PResult<'_, &str> {
+    take_while1(input, char::is_alphanumeric, Expected::Word)
+}
+
+/// Parses a value surrounded by opening and closing tags
+pub fn bracketed<'a, P>(
+    open: &'static str,
+    mut parser: P,
+    close: &'static str,
+) -> impl FnMut(&'a str) -> PResult<'a, P::Output>
+where
+    P: Parser<'a>,
+{
+    move |input| {
+        let (_, input) = tag(open)(input)?;
+        let (value, input) = parser.parse(input)?;
+        let (_, input) = tag(close)(input)?;
+
+        Ok((value, input))
+    }
+}
+
+/// Parses a value preceded by another, discarding the first
+pub fn preceded<'a, P1, P2>(
+    mut first: P1,
+    mut parser: P2,
+) -> impl FnMut(&'a str) -> PResult<'a, P2::Output>
+where
+    P1: Parser<'a>,
+    P2: Parser<'a>,
+{
+    move |input| {
+        let (_, input) = first.parse(input)?;
+
+        parser.parse(input)
+    }
+}
+
+/// Parses a value followed by another, discarding the second
+pub fn terminated<'a, P1, P2>(
+    mut parser: P1,
+    mut last: P2,
+) -> impl FnMut(&'a str) -> PResult<'a, P1::Output>
+where
+    P1: Parser<'a>,
+    P2: Parser<'a>,
+{
+    move |input| {
+        let (value, input) = parser.parse(input)?;
+        let (_, input) = last.parse(input)?;
+
+        Ok((value, input))
+    }
+}
+
+/// Maps the value of a parser
+pub fn map<'a, P, F, T>(mut parser: P, mut f: F) -> impl FnMut(&'a str) -> PResult<'a, T>
+where
+    P: Parser<'a>,
+    F: FnMut(P::Output) -> T,
+{
+    move |input| {
+        let (value, input) = parser.parse(input)?;
+
+        Ok((f(value), input))
+    }
+}
+
+/// Parses an optional value
+pub fn opt<'a, P>(mut parser: P) -> impl FnMut(&'a str) -> PResult<'a, Option<P::Output>>
+where
+    P: Parser<'a>,
+{
+    move |input| match parser.parse(input) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(e) if consumed(&e, input) => Err(e),
+        Err(_) => Ok((None, input)),
+    }
+}
+
+/// Parses one or more values
+pub fn many1<'a, P>(mut parser: P) -> impl FnMut(&'a str) -> PResult<'a, Vec<P::Output>>
+where
+    P: Parser<'a>,
+{
+    move |input| {
+        let (first, mut input) = parser.parse(input)?;
+        let mut values = vec![first];
+
+        loop {
+            match parser.parse(input) {
+                // Stop if the parser makes no progress
+                Ok((_, rest)) if rest.len() == input.len() => break,
+                Ok((value, rest)) => {
+                    values.push(value);
+                    input = rest;
+                }
+                Err(e) if consumed(&e, input) => return Err(e),
+                Err(_) => break,
+            }
+        }
+
+        Ok((values, input))
+    }
+}
+
+/// Parses zero or more values separated by a separator
+///
+/// A trailing separator is left unparsed
+pub fn sep_by<'a, P, S>(
+    mut parser: P,
+    mut sep: S,
+) -> impl FnMut(&'a str) -> PResult<'a, Vec<P::Output>>
+where
+    P: Parser<'a>,
+    S: Parser<'a>,
+{
+    move |input| match parser.parse(input) {
+        Ok((first, rest)) => sep_rest(&mut parser, &mut sep, false, first, rest),
+        Err(e) if consumed(&e, input) => Err(e),
+        Err(_) => Ok((Vec::new(), input)),
+    }
+}
+
+/// Parses one or more values separated by a separator
+///
+/// A trailing separator is left unparsed
+pub fn sep_by1<'a, P, S>(
+    mut parser: P,
+    mut sep: S,
+) -> impl FnMut(&'a str) -> PResult<'a, Vec<P::Output>>
+where
+    P: Parser<'a>,
+    S: Parser<'a>,
+{
+    move |input| {
+        let (first, rest) = parser.parse(input)?;
+
+        sep_rest(&mut parser, &mut sep, false, first, rest)
+    }
+}
+
+/// Parses one or more comma separated values
+///
+/// Unlike [`sep_by1`], a comma must be followed by a value
+pub fn comma_list<'a, P>(mut parser: P) -> impl FnMut(&'a str) -> PResult<'a, Vec<P::Output>>
+where
+    P: Parser<'a>,
+{
+    let mut sep = tag(",");
+
+    move |input| {
+        let (first, rest) = parser.parse(input)?;
+
+        sep_rest(&mut parser, &mut sep, true, first, rest)
+    }
+}
+
+/// Parses the separated values following the first
+///
+/// If `commit` is set, a value which fails to parse after a separator is an error
+fn sep_rest<'a, P, S>(
+    parser: &mut P,
+    sep: &mut S,
+    commit: bool,
+    first: P::Output,
+    mut input: &'a str,
+) -> PResult<'a, Vec<P::Output>>
+where
+    P: Parser<'a>,
+    S: Parser<'a>,
+{
+    let mut values = vec![first];
+
+    while let Ok((_, rest)) = sep.parse(input) {
+        match parser.parse(rest) {
+            // Stop if the separator and value make no progress
+            Ok((_, rest)) if rest.len() == input.len() => break,
+            Ok((value, rest)) => {
+                values.push(value);
+                input = rest;
+            }
+            Err(e) if commit || consumed(&e, rest) => return Err(e),
+            Err(_) => break,
+        }
+    }
+
+    Ok((values, input))
+}
+
+/// Returns true if a parser failed after consuming some of its input
+pub fn consumed(err: &ParseError, input: &str) -> bool {
+    err.token.as_ptr() as usize > input.as_ptr() as usize
+}
+
+/// Parses one or more characters matching a predicate
+fn take_while1(input: &str, pred: fn(char) -> bool, expected: Expected) -> PResult<'_, &str> {
+    let len = input
+        .char_indices()
+        .find(|(_, c)| !pred(*c))
+        .map_or(input.len(), |(i, _)| i);
+
+    if len == 0 {
+        Err(ParseError::at(input, expected))
+    } else {
+        Ok((&input[..len], &input[len..]))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,156 @@
+use super::*;
+
+#[test]
+fn integers() {
+    assert_eq!(int::<u8>()("12,3"), Ok((12, ",3")));
+    assert_eq!(int::<i32>()("-7"), Ok((-7, "")));
+
+    let err = int::<u8>()("300 1").unwrap_err();
+    assert_eq!((err.token, err.expected), ("300", Expected::InRange));
+
+    let err = int::<u8>()("x1").unwrap_err();
+    assert_eq!((err.token, err.expected), ("x", Expected::Integer));
+}
+
+#[test]
+fn lists() {
+    assert_eq!(
+        comma_list(int::<u8>())("1,2,3 x"),
+        Ok((vec![1, 2, 3], " x"))
+    );
+    assert!(comma_list(int::<u8>())("1,").is_err());
+    assert_eq!(sep_by1(int::<u8>(), tag(","))("1,"), Ok((vec![1], ",")));
+    assert_eq!(
+        sep_by(word, ws)("ab cd  ef:"),
+        Ok((vec!["ab", "cd", "ef"], ":"))
+    );
+    assert_eq!(sep_by(word, ws)(":"), Ok((vec![], ":")));
+    assert!(sep_by1(word, ws)(":").is_err());
+
+    // Stops if the separator and value consume nothing
+    assert_eq!(
+        sep_by1(opt(tag("x")), tag(""))("ab"),
+        Ok((vec![None], "ab"))
+    );
+}
+
+#[test]
+fn sequences() {
+    let line = "[.##.] (3) (1,3) {3,5,4,7}";
+
+    let indicators = bracketed("[", many1(map(one_of(".#"), |c| c == '#')), "]");
+    let schematic = bracketed("(", comma_list(int::<u8>()), ")");
+    let joltages = bracketed("{", comma_list(int::<u16>()), "}");
+
+    let (indicators, _, schematics, _, joltages) =
+        parse_all((indicators, ws, sep_by1(schematic, ws), ws, joltages), line).unwrap();
+
+    assert_eq!(indicators, vec![false, true, true, false]);
+    assert_eq!(schematics, vec![vec![3], vec![1, 3]]);
+    assert_eq!(joltages, vec![3, 5, 4, 7]);
+}
+
+#[test]
+fn errors() {
+    let schematic = bracketed("(", comma_list(int::<u8>()), ")");
+
+    // Error inside a repeated item is reported at the bad token
+    let err = sep_by1(schematic, ws)("(1) (2,x) {1}").unwrap_err();
+    assert_eq!((err.token, err.expected), ("x", Expected::Integer));
+
+    // Trailing input
+    assert!(parse_all(terminated(word, tag(":")), "abc:def").is_err());
+
+    // Tag at end of input
+    let err = tag(":")("").unwrap_err();
+    assert_eq!((err.token, err.expected), ("", Expected::Tag(":")));
+}
+
+#[test]
+fn token_error() {
+    let err = crate::input::try_parse_test_vec("1x1\n2x\n", |line| {
+        parse_all((int::<u8>(), preceded(tag("x"), int::<u8>())), line)
+    })
+    .unwrap_err();
+
+    assert!(matches!(
+        err,
+        crate::Error::Transform {
+            line: 2,
+            column: Some(3),
+            ..
+        }
+    ));
+}
+
+mod derive {
+    use aoc_derive::AocParse;
+
+    use crate::input::try_parse_test_vec;
+
+    #[derive(Debug, PartialEq, AocParse)]
+    #[aoc(format = "{x},{y}")]
+    struct Coord {
+        x: i64,
+        y: i64,
+    }
+
+    #[derive(Debug, PartialEq, AocParse)]
+    enum Dir {
+        #[aoc(format = "L")]
+        Left,
+        #[aoc(format = "R")]
+        Right,
+    }
+
+    #[derive(Debug, PartialEq, AocParse)]
+    #[aoc(format = "{dir}{amount}")]
+    struct Rotation {
+        dir: Dir,
+        amount: u16,
+    }
+
+    #[derive(Debug, PartialEq, AocParse)]
+    enum Move {
+        #[aoc(format = "up {0}")]
+        Up(u8),
+        #[aoc(format = "to ({0}, {1}) {{{2}}}")]
+        To(u8, u8, u8),
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!("-3,14".parse::<Coord>().unwrap(), Coord { x: -3, y: 14 });
+        assert_eq!(
+            "R42".parse::<Rotation>().unwrap(),
+            Rotation {
+                dir: Dir::Right,
+                amount: 42
+            }
+        );
+        assert_eq!("up 5".parse::<Move>().unwrap(), Move::Up(5));
+        assert_eq!("to (1, 2) {3}".parse::<Move>().unwrap(), Move::To(1, 2, 3));
+    }
+
+    #[test]
+    fn errors() {
+        let err = "1;2".parse::<Coord>().unwrap_err();
+        assert_eq!(err.to_string(), "Expected \",\"");
+
+        let err = "X42".parse::<Rotation>().unwrap_err();
+        assert_eq!(err.to_string(), "Expected one of the Dir formats");
+
+        // A variant failing after its first literal doesn't fall through to the next
+        let err = "up x".parse::<Move>().unwrap_err();
+        assert_eq!(err.to_string(), "Expected an integer");
+
+        let err = "R42 ".parse::<Rotation>().unwrap_err();
+        assert_eq!(err.to_string(), "Unexpected trailing input");
+
+        let err = try_parse_test_vec("1,2\n3,x\n", str::parse::<Coord>).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Expected an integer\n --> 2:3\n  |\n2 | 3,x\n  |   ^"
+        );
+    }
+}
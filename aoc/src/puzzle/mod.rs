@@ -0,0 +1,76 @@
+use std::{
+    env, fmt,
+    path::{Path, PathBuf},
+};
+
+include!("year.rs");
+
+/// Environment variable overriding the year of puzzles given by day number alone
+pub const YEAR_VAR: &str = "AOC_YEAR";
+
+/// A puzzle, identified by event year and day
+///
+/// Converting a day number with `From<usize>` uses the default year, so functions
+/// taking `impl Into<Puzzle>` accept either.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: usize,
+    pub day: usize,
+}
+
+impl Puzzle {
+    /// Creates a puzzle for a year and day
+    pub fn new(year: usize, day: usize) -> Self {
+        Self { year, day }
+    }
+
+    /// Returns the default year, from `AOC_YEAR` if set or `DEFAULT_YEAR` otherwise
+    pub fn default_year() -> usize {
+        env::var(YEAR_VAR)
+            .ok()
+            .and_then(|year| year.trim().parse().ok())
+            .unwrap_or(DEFAULT_YEAR)
+    }
+
+    /// Returns the name of the day's input file, `dayNN.txt`
+    pub fn file_name(&self) -> String {
+        format!("day{:02}.txt", self.day)
+    }
+
+    /// Returns the directory in an input directory the puzzle's files are read from and
+    /// written to
+    ///
+    /// See [`year_dir`], which decides whether the year uses the flat layout.
+    pub fn input_dir(&self, dir: &Path) -> PathBuf {
+        year_dir(dir, self.year)
+    }
+}
+
+impl From<usize> for Puzzle {
+    fn from(day: usize) -> Self {
+        Self::new(Self::default_year(), day)
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+/// Returns the directory in an input directory a year's files are read from and written to
+///
+/// This is `<dir>/<year>`, except for the default year which keeps using the flat
+/// layout until its year directory is created. Other years never use the flat layout.
+pub fn year_dir(dir: &Path, year: usize) -> PathBuf {
+    let year_dir = dir.join(year.to_string());
+
+    if year == Puzzle::default_year() && !year_dir.is_dir() {
+        dir.to_path_buf()
+    } else {
+        year_dir
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,39 @@
+use super::*;
+use crate::test_util::TempDir;
+
+#[test]
+fn paths() {
+    let puzzle = Puzzle::new(2024, 3);
+
+    assert_eq!(puzzle.file_name(), "day03.txt");
+    assert_eq!(puzzle.to_string(), "2024 day 3");
+    assert_eq!(
+        puzzle.input_dir(Path::new("inputs")),
+        PathBuf::from("inputs/2024")
+    );
+}
+
+#[test]
+fn year_dirs() {
+    let temp = TempDir::new("puzzle");
+    let dir = temp.path();
+
+    let year = Puzzle::default_year();
+    let other = year - 1;
+
+    // The default year uses the flat layout until its directory exists
+    assert_eq!(year_dir(dir, year), dir);
+    assert_eq!(year_dir(dir, other), dir.join(other.to_string()));
+
+    // Other years never read the flat layout
+    std::fs::write(dir.join("day03.txt"), "flat").unwrap();
+
+    let puzzle = Puzzle::new(other, 3);
+    let path = puzzle.input_dir(dir).join(puzzle.file_name());
+
+    assert_eq!(path, dir.join(other.to_string()).join("day03.txt"));
+    assert!(!path.exists());
+
+    std::fs::create_dir_all(dir.join(year.to_string())).unwrap();
+    assert_eq!(year_dir(dir, year), dir.join(year.to_string()));
+}
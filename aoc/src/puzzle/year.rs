@@ -0,0 +1,4 @@
+/// Year puzzles given by day number alone belong to, unless overridden by `AOC_YEAR`
+///
+/// Shared with the build script, which embeds inputs for this year
+pub const DEFAULT_YEAR: usize = 2025;
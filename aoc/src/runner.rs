@@ -0,0 +1,100 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use crate::{input::INPUT_VAR, puzzle::YEAR_VAR, Error, Puzzle};
+
+/// Result of running a day's binary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    /// Answers printed for each part
+    pub answers: [Option<String>; 2],
+    /// Wall clock time taken by the binary
+    pub elapsed: Duration,
+}
+
+/// Builds a day's release binary, returning its path
+pub fn build_day(day: usize) -> Result<PathBuf, Error> {
+    let name = format!("day{day:02}");
+
+    let output = Command::new(cargo())
+        .args(["build", "-q", "--release", "--bin", &name])
+        .output()
+        .map_err(|source| Error::Io {
+            path: PathBuf::from(cargo()),
+            source,
+        })?;
+
+    if !output.status.success() {
+        return Err(Error::Run {
+            day,
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+
+    let target = env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("target"));
+
+    Ok(target.join("release").join(name))
+}
+
+/// Runs a day's binary on an input file, or the puzzle's default input if None
+pub fn run_day(
+    puzzle: impl Into<Puzzle>,
+    binary: &Path,
+    input: Option<&Path>,
+) -> Result<Run, Error> {
+    let puzzle = puzzle.into();
+    let mut command = Command::new(binary);
+
+    command.env(YEAR_VAR, puzzle.year.to_string());
+
+    if let Some(input) = input {
+        command.env(INPUT_VAR, input);
+    }
+
+    let start = Instant::now();
+
+    let output = command.output().map_err(|source| Error::Io {
+        path: binary.to_path_buf(),
+        source,
+    })?;
+
+    let elapsed = start.elapsed();
+
+    if !output.status.success() {
+        return Err(Error::Run {
+            day: puzzle.day,
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+
+    Ok(Run {
+        answers: parse_answers(&String::from_utf8_lossy(&output.stdout)),
+        elapsed,
+    })
+}
+
+/// Extracts the answers from the `Part N: answer` lines of a day's output
+pub fn parse_answers(output: &str) -> [Option<String>; 2] {
+    let mut answers = [None, None];
+
+    for line in output.lines() {
+        for (part, answer) in answers.iter_mut().enumerate() {
+            if let Some(value) = line.strip_prefix(&format!("Part {}: ", part + 1)) {
+                *answer = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    answers
+}
+
+/// Returns the cargo command to use
+fn cargo() -> String {
+    env::var("CARGO").unwrap_or_else(|_| "cargo".to_string())
+}
@@ -0,0 +1,33 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+/// Temporary directory for a test, removed with its contents when dropped
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Creates a new empty directory, unique to a test name and this process
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("aoc-{name}-{}", process::id()));
+
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+
+        Self { path }
+    }
+
+    /// Returns the path of the directory
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
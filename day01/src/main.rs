@@ -1,87 +1,78 @@
 use std::error::Error;
 
-use aoc::input::parse_input_vec;
+use aoc::{input::try_fold_input, parse::AocParse};
 
 fn main() -> Result<(), Box<dyn Error>> {
-    // Get input
-    let input = parse_input_vec(1, input_transform)?;
+    // Run parts in a single pass over the input
+    let dial = try_fold_input(1, str::parse, Dial::default(), Dial::turn)?;
 
-    // Run parts
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    println!("Part 1: {}", dial.part1);
+    println!("Part 2: {}", dial.part2);
 
     Ok(())
 }
 
-fn part1(input: &[InputEnt]) -> u64 {
-    let mut cur_pos: i64 = 50;
-    let mut password: u64 = 0;
-
-    for movement in input {
-        match movement {
-            Rotation::Left(amount) => cur_pos = (cur_pos - *amount as i64).rem_euclid(100),
-            Rotation::Right(amount) => cur_pos = (cur_pos + *amount as i64).rem_euclid(100),
-        }
+#[derive(Debug, Clone, Copy)]
+struct Dial {
+    pos: i64,
+    /// Number of times a rotation finished at zero
+    part1: u64,
+    /// Number of times the dial passed zero
+    part2: u64,
+}
 
-        if cur_pos == 0 {
-            password += 1;
+impl Default for Dial {
+    fn default() -> Self {
+        Self {
+            pos: 50,
+            part1: 0,
+            part2: 0,
         }
     }
-
-    password
 }
 
-fn part2(input: &[InputEnt]) -> u64 {
-    let mut cur_pos: i64 = 50;
-    let mut password: u64 = 0;
-
-    for movement in input {
+impl Dial {
+    fn turn(mut self, movement: InputEnt) -> Self {
         let count = match movement {
-            Rotation::Left(amount) => -(*amount as i64),
-            Rotation::Right(amount) => *amount as i64,
+            Rotation::Left(amount) => -(amount as i64),
+            Rotation::Right(amount) => amount as i64,
         };
 
         let add = count.signum();
 
         for _ in 0..count.abs() {
-            cur_pos += add;
+            self.pos += add;
 
-            match cur_pos {
-                -1 => cur_pos = 99,
-                100 => cur_pos = 0,
+            match self.pos {
+                -1 => self.pos = 99,
+                100 => self.pos = 0,
                 _ => (),
             }
 
-            if cur_pos == 0 {
-                password += 1;
+            if self.pos == 0 {
+                self.part2 += 1;
             }
         }
-    }
 
-    password
+        if self.pos == 0 {
+            self.part1 += 1;
+        }
+
+        self
+    }
 }
 
 // Input parsing
 
-#[derive(Debug)]
+#[derive(Debug, AocParse)]
 enum Rotation {
+    #[aoc(format = "L{0}")]
     Left(u16),
+    #[aoc(format = "R{0}")]
     Right(u16),
 }
 
 type InputEnt = Rotation;
 
-fn input_transform(line: &str) -> InputEnt {
-    let (dir, amount) = line.split_at(1);
-
-    let amount = amount.parse::<u16>().unwrap();
-
-    match dir {
-        "L" => Rotation::Left(amount),
-        "R" => Rotation::Right(amount),
-        _ => panic!("Invalid direction {dir}"),
-    }
-}
-
 #[cfg(test)]
 mod tests;
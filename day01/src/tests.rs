@@ -1,4 +1,4 @@
-use aoc::input::parse_test_vec;
+use aoc::input::try_fold_test;
 
 use super::*;
 
@@ -14,32 +14,28 @@ L99
 R14
 L82";
 
+fn run(test: &str) -> Dial {
+    try_fold_test(test, str::parse, Dial::default(), Dial::turn).unwrap()
+}
+
 #[test]
 fn test1() {
-    let input = parse_test_vec(EXAMPLE1, input_transform).unwrap();
-    assert_eq!(part1(&input), 3);
-    assert_eq!(part2(&input), 6);
+    let dial = run(EXAMPLE1);
+    assert_eq!(dial.part1, 3);
+    assert_eq!(dial.part2, 6);
 }
 
 #[test]
 fn test2() {
-    let input = parse_test_vec("R1000", input_transform).unwrap();
-    assert_eq!(part2(&input), 10);
+    assert_eq!(run("R1000").part2, 10);
 }
 
 #[test]
 fn test3() {
-    let input = parse_test_vec("R1050", input_transform).unwrap();
-    assert_eq!(part2(&input), 11);
+    assert_eq!(run("R1050").part2, 11);
 }
 
 #[test]
 fn test4() {
-    let input = parse_test_vec(
-        "L50
-R100",
-        input_transform,
-    )
-    .unwrap();
-    assert_eq!(part2(&input), 2);
+    assert_eq!(run("L50\nR100").part2, 2);
 }
@@ -1,3 +1,5 @@
+use aoc::input::parse_test_line;
+
 use super::*;
 
 const EXAMPLE1: &str = "\
@@ -8,7 +10,7 @@ const EXAMPLE1: &str = "\
 
 #[test]
 fn test1() {
-    let input = input_transform(EXAMPLE1);
+    let input = parse_test_line(EXAMPLE1, input_transform).unwrap();
     assert_eq!(part1(&input), 1227775554);
     assert_eq!(part2(&input), 4174379265);
 }
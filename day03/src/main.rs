@@ -1,77 +1,77 @@
 use std::error::Error;
 
-use aoc::input::parse_input_vec;
+use aoc::input::{Input, parse_input_iter};
 
 fn main() -> Result<(), Box<dyn Error>> {
     // Get input
-    let input = parse_input_vec(3, input_transform)?;
+    let input = Input::new(3)?;
 
-    // Run parts
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    // Run parts in a single pass over the input
+    let (part1, part2) = run(&input)?;
+
+    println!("Part 1: {part1}");
+    println!("Part 2: {part2}");
 
     Ok(())
 }
 
-fn part1(input: &[InputEnt]) -> u64 {
+fn run(input: &Input) -> Result<(u64, u64), aoc::Error> {
     // Iterate banks
-    input
-        .iter()
-        .map(|bank| {
-            // Get highest digit (not last)
-            let (p1, d1) =
-                bank.iter()
-                    .rev()
-                    .skip(1)
-                    .rev()
-                    .enumerate()
-                    .fold(
-                        (0, 0),
-                        |(mi, mv), (i, v)| {
-                            if *v > mv { (i, *v) } else { (mi, mv) }
-                        },
-                    );
-
-            // Get highest digit in the reaminder
-            let d2 = bank.iter().skip(p1 + 1).max().unwrap();
-
-            // Calculate joltage
-            ((d1 * 10) + d2) as u64
-        })
-        .sum()
+    parse_input_iter(input, input_transform).try_fold((0, 0), |(sum1, sum2), bank| {
+        let bank = bank?;
+
+        Ok((sum1 + part1(&bank), sum2 + part2(&bank)))
+    })
 }
 
-fn part2(input: &[InputEnt]) -> u64 {
-    input
+fn part1(bank: &[u8]) -> u64 {
+    // Get highest digit (not last)
+    let (p1, d1) = bank
         .iter()
-        .map(|bank| {
-            // Get 12 digits
-            (0..12)
-                .fold((0, 0), |(pos, sum), digit| {
-                    // Get highest digit from remaining digits, leaving enough for later iterations
-                    let (new_pos, d) = bank
-                        .iter()
-                        .enumerate()
-                        .skip(pos)
-                        .rev()
-                        .skip(11 - digit)
-                        .rev()
-                        .fold(
-                            (0, 0),
-                            |(mi, mv), (i, v)| {
-                                if *v > mv { (i, *v) } else { (mi, mv) }
-                            },
-                        );
-
-                    // Return new position and sum
-                    (
-                        new_pos + 1,
-                        sum + (d as u64 * 10u64.pow((11 - digit) as u32)),
-                    )
-                })
-                .1
+        .rev()
+        .skip(1)
+        .rev()
+        .enumerate()
+        .fold(
+            (0, 0),
+            |(mi, mv), (i, v)| {
+                if *v > mv { (i, *v) } else { (mi, mv) }
+            },
+        );
+
+    // Get highest digit in the reaminder
+    let d2 = bank.iter().skip(p1 + 1).max().unwrap();
+
+    // Calculate joltage
+    ((d1 * 10) + d2) as u64
+}
+
+fn part2(bank: &[u8]) -> u64 {
+    // Get 12 digits
+    (0..12)
+        .fold((0, 0), |(pos, sum), digit| {
+            // Get highest digit from remaining digits, leaving enough for later iterations
+            let (new_pos, d) = bank
+                .iter()
+                .enumerate()
+                .skip(pos)
+                .rev()
+                .skip(11 - digit)
+                .rev()
+                .fold(
+                    (0, 0),
+                    |(mi, mv), (i, v)| {
+                        if *v > mv { (i, *v) } else { (mi, mv) }
+                    },
+                );
+
+            // Return new position and sum
+            (
+                new_pos + 1,
+                sum + (d as u64 * 10u64.pow((11 - digit) as u32)),
+            )
         })
-        .sum()
+        .1
 }
 
 // Input parsing
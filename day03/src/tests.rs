@@ -1,4 +1,4 @@
-use aoc::input::parse_test_vec;
+use aoc::input::Input;
 
 use super::*;
 
@@ -10,7 +10,6 @@ const EXAMPLE1: &str = "\
 
 #[test]
 fn test1() {
-    let input = parse_test_vec(EXAMPLE1, input_transform).unwrap();
-    assert_eq!(part1(&input), 357);
-    assert_eq!(part2(&input), 3121910778619);
+    let input = Input::new_from_test(EXAMPLE1).unwrap();
+    assert_eq!(run(&input).unwrap(), (357, 3121910778619));
 }
@@ -1,10 +1,10 @@
 use std::error::Error;
 
-use aoc::input::parse_input_vec;
+use aoc::{grid::Grid, input::try_parse_input_grid};
 
 fn main() -> Result<(), Box<dyn Error>> {
     // Get input
-    let input = parse_input_vec(4, input_transform)?;
+    let input = try_parse_input_grid(4, input_transform)?;
 
     // Run parts
     println!("Part 1: {}", part1(&input));
@@ -13,26 +13,17 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn part1(input: &[InputEnt]) -> u64 {
+fn part1(input: &Grid<State>) -> u64 {
     input
         .iter()
-        .enumerate()
-        .map(|(y, row)| {
-            row.iter().enumerate().fold(0, |acc, (x, state)| {
-                if *state == State::Paper && adjacent_count(input, x, y) < 4 {
-                    acc + 1
-                } else {
-                    acc
-                }
-            })
-        })
-        .sum()
+        .filter(|&((x, y), state)| *state == State::Paper && adjacent_count(input, x, y) < 4)
+        .count() as u64
 }
 
-fn part2(input: &[InputEnt]) -> u64 {
-    let mut board = input.to_vec();
-    let w = board[0].len();
-    let h = board.len();
+fn part2(input: &Grid<State>) -> u64 {
+    let mut board = input.clone();
+    let w = board.width();
+    let h = board.height();
     let mut removed = 0;
 
     loop {
@@ -40,8 +31,8 @@ fn part2(input: &[InputEnt]) -> u64 {
 
         for y in 0..h {
             for x in 0..w {
-                if board[y][x] == State::Paper && adjacent_count(&board, x, y) < 4 {
-                    board[y][x] = State::Empty;
+                if board[(x, y)] == State::Paper && adjacent_count(&board, x, y) < 4 {
+                    board[(x, y)] = State::Empty;
                     this_removed += 1;
                 }
             }
@@ -57,30 +48,13 @@ fn part2(input: &[InputEnt]) -> u64 {
     removed
 }
 
-fn adjacent_count(input: &[InputEnt], x: usize, y: usize) -> usize {
-    adjacent_coords(input, x, y)
-        .filter(|&(x, y)| input[y][x] == State::Paper)
+fn adjacent_count(input: &Grid<State>, x: usize, y: usize) -> usize {
+    input
+        .neighbours8(x, y)
+        .filter(|&pos| input[pos] == State::Paper)
         .count()
 }
 
-fn adjacent_coords(input: &[InputEnt], x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
-    let x = x as isize;
-    let y = y as isize;
-
-    let w = input[0].len() as isize;
-    let h = input.len() as isize;
-
-    ((y - 1)..=(y + 1)).flat_map(move |ty| {
-        ((x - 1)..=(x + 1)).filter_map(move |tx| {
-            if ty >= 0 && ty < h && tx >= 0 && tx < w && !(tx == x && ty == y) {
-                Some((tx as usize, ty as usize))
-            } else {
-                None
-            }
-        })
-    })
-}
-
 // Input parsing
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -89,16 +63,12 @@ enum State {
     Paper,
 }
 
-type InputEnt = Vec<State>;
-
-fn input_transform(line: &str) -> InputEnt {
-    line.chars()
-        .map(|c| match c {
-            '.' => State::Empty,
-            '@' => State::Paper,
-            c => panic!("Invalid char '{c}'"),
-        })
-        .collect()
+fn input_transform(c: char) -> Result<State, String> {
+    match c {
+        '.' => Ok(State::Empty),
+        '@' => Ok(State::Paper),
+        c => Err(format!("Invalid char '{c}'")),
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-use aoc::input::parse_test_vec;
+use aoc::input::try_parse_test_grid;
 
 use super::*;
 
@@ -17,7 +17,7 @@ const EXAMPLE1: &str = "\
 
 #[test]
 fn test1() {
-    let input = parse_test_vec(EXAMPLE1, input_transform).unwrap();
+    let input = try_parse_test_grid(EXAMPLE1, input_transform).unwrap();
     assert_eq!(part1(&input), 13);
     assert_eq!(part2(&input), 43);
 }
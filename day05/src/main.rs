@@ -1,10 +1,13 @@
 use std::{error::Error, ops::RangeInclusive};
 
-use aoc::input::parse_input;
+use aoc::{
+    input::{Section, parse_input_sections},
+    scan,
+};
 
 fn main() -> Result<(), Box<dyn Error>> {
     // Get input
-    let (ranges, ingredients) = parse_input(5, input_transform)?;
+    let (ranges, ingredients) = parse_input_sections(5, (ranges_transform, ingredients_transform))?;
 
     // Run parts
     println!("Part 1: {}", part1(&ranges, &ingredients));
@@ -101,25 +104,21 @@ fn combine_ranges(
 
 // Input parsing
 
-type InputEnt = (Vec<RangeInclusive<u64>>, Vec<u64>);
-
-fn input_transform(file: &str) -> InputEnt {
-    let (ranges, ingredients) = file.split_once("\n\n").unwrap();
-
-    let ranges = ranges
+fn ranges_transform(section: Section) -> Vec<RangeInclusive<u64>> {
+    section
         .lines()
         .map(|line| {
-            let (from, to) = line.split_once("-").unwrap();
-            (from.parse::<u64>().unwrap())..=(to.parse::<u64>().unwrap())
+            let (from, to) = scan!(line, "{}-{}", u64, u64).unwrap();
+            from..=to
         })
-        .collect::<Vec<_>>();
+        .collect()
+}
 
-    let ingredients = ingredients
+fn ingredients_transform(section: Section) -> Vec<u64> {
+    section
         .lines()
-        .map(|line| line.parse::<u64>().unwrap())
-        .collect();
-
-    (ranges, ingredients)
+        .map(|line| scan!(line, "{}", u64).unwrap().0)
+        .collect()
 }
 
 #[cfg(test)]
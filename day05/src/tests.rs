@@ -1,3 +1,5 @@
+use aoc::input::parse_test_sections;
+
 use super::*;
 
 const EXAMPLE1: &str = "\
@@ -16,7 +18,8 @@ const EXAMPLE1: &str = "\
 
 #[test]
 fn test1() {
-    let (ranges, ingredients) = input_transform(EXAMPLE1);
+    let (ranges, ingredients) =
+        parse_test_sections(EXAMPLE1, (ranges_transform, ingredients_transform)).unwrap();
     assert_eq!(part1(&ranges, &ingredients), 3);
     assert_eq!(part2(&ranges), 14);
 }
@@ -1,3 +1,5 @@
+use aoc::input::parse_test;
+
 use super::*;
 
 const EXAMPLE1: &str = "\
@@ -9,10 +11,10 @@ const EXAMPLE1: &str = "\
 
 #[test]
 fn test1() {
-    let (numbers, operators) = input_transform1(EXAMPLE1);
+    let (numbers, operators) = parse_test(EXAMPLE1, input_transform1).unwrap();
     assert_eq!(part1(&numbers, &operators), 4277556);
 
-    let (numbers, operators) = input_transform2(EXAMPLE1);
+    let (numbers, operators) = parse_test(EXAMPLE1, input_transform2).unwrap();
     println!("{:?}", numbers);
     assert_eq!(part2(&numbers, &operators), 3263827);
 }
@@ -3,11 +3,11 @@ use std::{
     error::Error,
 };
 
-use aoc::input::parse_input_vec;
+use aoc::{grid::Grid, input::try_parse_input_grid};
 
 fn main() -> Result<(), Box<dyn Error>> {
     // Get input
-    let input = parse_input_vec(7, input_transform)?;
+    let input = try_parse_input_grid(7, input_transform)?;
 
     // Run parts
     println!("Part 1: {}", part1(&input));
@@ -16,9 +16,9 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn part1(input: &[InputEnt]) -> u64 {
+fn part1(input: &Grid<Space>) -> u64 {
     // Find start
-    let (sx, mut y) = find_start(input);
+    let (sx, mut y) = input.find(&Space::Start).unwrap();
 
     let mut beams = HashSet::new();
 
@@ -28,11 +28,11 @@ fn part1(input: &[InputEnt]) -> u64 {
 
     y += 1;
 
-    while y < input.len() {
+    while y < input.height() {
         let mut next_beams = HashSet::new();
 
         for x in beams {
-            match input[y][x] {
+            match input[(x, y)] {
                 Space::Splitter => {
                     next_beams.insert(x - 1);
                     next_beams.insert(x + 1);
@@ -52,9 +52,9 @@ fn part1(input: &[InputEnt]) -> u64 {
     splits
 }
 
-fn part2(input: &[InputEnt]) -> u64 {
+fn part2(input: &Grid<Space>) -> u64 {
     // Find start
-    let (sx, mut y) = find_start(input);
+    let (sx, mut y) = input.find(&Space::Start).unwrap();
 
     let mut beams = HashMap::new();
 
@@ -62,11 +62,11 @@ fn part2(input: &[InputEnt]) -> u64 {
 
     y += 1;
 
-    while y < input.len() {
+    while y < input.height() {
         let mut next_beams = HashMap::new();
 
         for (x, count) in beams {
-            match input[y][x] {
+            match input[(x, y)] {
                 Space::Splitter => {
                     *next_beams.entry(x - 1).or_insert(0) += count;
                     *next_beams.entry(x + 1).or_insert(0) += count;
@@ -85,26 +85,6 @@ fn part2(input: &[InputEnt]) -> u64 {
     beams.values().sum::<usize>() as u64
 }
 
-fn find_start(input: &[InputEnt]) -> (usize, usize) {
-    input
-        .iter()
-        .enumerate()
-        .filter_map(|(y, line)| {
-            line.iter()
-                .enumerate()
-                .filter_map(|(x, s)| {
-                    if *s == Space::Start {
-                        Some((x, y))
-                    } else {
-                        None
-                    }
-                })
-                .next()
-        })
-        .next()
-        .unwrap()
-}
-
 // Input parsing
 
 #[derive(Debug, PartialEq, Eq)]
@@ -114,17 +94,13 @@ enum Space {
     Splitter,
 }
 
-type InputEnt = Vec<Space>;
-
-fn input_transform(line: &str) -> InputEnt {
-    line.chars()
-        .map(|c| match c {
-            '.' => Space::Empty,
-            '^' => Space::Splitter,
-            'S' => Space::Start,
-            _ => panic!(),
-        })
-        .collect()
+fn input_transform(c: char) -> Result<Space, String> {
+    match c {
+        '.' => Ok(Space::Empty),
+        '^' => Ok(Space::Splitter),
+        'S' => Ok(Space::Start),
+        c => Err(format!("Invalid char '{c}'")),
+    }
 }
 
 #[cfg(test)]
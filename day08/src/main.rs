@@ -1,10 +1,13 @@
 use std::{collections::HashSet, error::Error};
 
-use aoc::input::parse_input_vec;
+use aoc::{
+    input::{TokenError, try_parse_input_vec},
+    scan,
+};
 
 fn main() -> Result<(), Box<dyn Error>> {
     // Get input
-    let junctions = parse_input_vec(8, input_transform)?;
+    let junctions = try_parse_input_vec(8, input_transform)?;
 
     // Build edges
     let edges = build_edges(&junctions);
@@ -128,7 +131,7 @@ fn build_edges(junctions: &[Junction]) -> Vec<(usize, usize, f64)> {
 // Input parsing
 
 struct Junction {
-    coords: Vec<u64>,
+    coords: [u64; 3],
 }
 
 impl Junction {
@@ -143,10 +146,10 @@ impl Junction {
     }
 }
 
-fn input_transform(line: &str) -> Junction {
-    Junction {
-        coords: line.split(',').map(|p| p.parse::<u64>().unwrap()).collect(),
-    }
+fn input_transform(line: &str) -> Result<Junction, TokenError> {
+    let (x, y, z) = scan!(line, "{},{},{}", u64, u64, u64)?;
+
+    Ok(Junction { coords: [x, y, z] })
 }
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-use aoc::input::parse_test_vec;
+use aoc::input::try_parse_test_vec;
 
 use super::*;
 
@@ -27,7 +27,7 @@ const EXAMPLE1: &str = "\
 
 #[test]
 fn test1() {
-    let junctions = parse_test_vec(EXAMPLE1, input_transform).unwrap();
+    let junctions = try_parse_test_vec(EXAMPLE1, input_transform).unwrap();
 
     let edges = build_edges(&junctions);
 
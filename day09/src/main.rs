@@ -1,10 +1,10 @@
 use std::error::Error;
 
-use aoc::input::parse_input_vec;
+use aoc::{input::try_parse_input_vec, parse::AocParse};
 
 fn main() -> Result<(), Box<dyn Error>> {
     // Get input
-    let input = parse_input_vec(9, input_transform)?;
+    let input = try_parse_input_vec(9, str::parse::<Coord>)?;
 
     // Run parts
     println!("Part 1: {}", part1(&input));
@@ -112,20 +112,12 @@ fn line_segment_intersects_rect(l1: &Coord, l2: &Coord, rmin: &Coord, rmax: &Coo
 
 // Input parsing
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, AocParse)]
+#[aoc(format = "{x},{y}")]
 struct Coord {
     x: i64,
     y: i64,
 }
 
-fn input_transform(line: &str) -> Coord {
-    let (x, y) = line.split_once(',').unwrap();
-
-    Coord {
-        x: x.parse::<i64>().unwrap(),
-        y: y.parse::<i64>().unwrap(),
-    }
-}
-
 #[cfg(test)]
 mod tests;
@@ -5,11 +5,11 @@ use std::{
     io::Write,
 };
 
-use aoc::input::parse_input_vec;
+use aoc::{input::try_parse_input_vec, parse::AocParse};
 
 fn main() -> Result<(), Box<dyn Error>> {
     // Get input
-    let input = parse_input_vec(9, input_transform)?;
+    let input = try_parse_input_vec(9, str::parse::<Coord>)?;
 
     // Run part 1
     let (p1_1, p1_2) = part1(&input);
@@ -209,17 +209,9 @@ fn draw_rect(file: &mut File, p1: Coord, p2: Coord, colour: &str) -> Result<(),
 
 // Input parsing
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, AocParse)]
+#[aoc(format = "{x},{y}")]
 struct Coord {
     x: i64,
     y: i64,
 }
-
-fn input_transform(line: &str) -> Coord {
-    let (x, y) = line.split_once(',').unwrap();
-
-    Coord {
-        x: x.parse::<i64>().unwrap(),
-        y: y.parse::<i64>().unwrap(),
-    }
-}
@@ -3,11 +3,14 @@ use std::{
     error::Error,
 };
 
-use aoc::input::parse_input_vec;
+use aoc::{
+    input::{TokenError, try_parse_input_vec},
+    parse::{bracketed, comma_list, int, many1, map, one_of, parse_all, sep_by1, ws},
+};
 
 fn main() -> Result<(), Box<dyn Error>> {
     // Get input
-    let input = parse_input_vec(10, input_transform)?;
+    let input = try_parse_input_vec(10, input_transform)?;
 
     // Run parts
     println!("Part 1: {}", part1(&input));
@@ -148,42 +151,19 @@ struct Machine {
     joltages: Vec<u16>,
 }
 
-fn input_transform(line: &str) -> Machine {
-    let mut iter = line.split_ascii_whitespace();
-
-    let indicators: Vec<bool> = iter
-        .next()
-        .unwrap()
-        .chars()
-        .filter_map(|c| match c {
-            '.' => Some(false),
-            '#' => Some(true),
-            _ => None,
-        })
-        .collect();
-
-    let joltages = iter
-        .next_back()
-        .unwrap()
-        .trim_matches(|c| c == '{' || c == '}')
-        .split(',')
-        .map(|nstr| nstr.parse::<u16>().unwrap())
-        .collect::<Vec<_>>();
+fn input_transform(line: &str) -> Result<Machine, TokenError> {
+    let indicators = bracketed("[", many1(map(one_of(".#"), |c| c == '#')), "]");
+    let schematic = bracketed("(", comma_list(int::<u8>()), ")");
+    let joltages = bracketed("{", comma_list(int::<u16>()), "}");
 
-    let schematics = iter
-        .map(|s| {
-            s.trim_matches(|c| c == '(' || c == ')')
-                .split(',')
-                .map(|nstr| nstr.parse::<u8>().unwrap())
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
+    let (indicators, _, schematics, _, joltages) =
+        parse_all((indicators, ws, sep_by1(schematic, ws), ws, joltages), line)?;
 
-    Machine {
+    Ok(Machine {
         indicators,
         schematics,
         joltages,
-    }
+    })
 }
 
 #[cfg(test)]
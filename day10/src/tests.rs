@@ -1,4 +1,4 @@
-use aoc::input::parse_test_vec;
+use aoc::input::try_parse_test_vec;
 
 use super::*;
 
@@ -10,7 +10,7 @@ const EXAMPLE1: &str = "\
 
 #[test]
 fn test1() {
-    let input = parse_test_vec(EXAMPLE1, input_transform).unwrap();
+    let input = try_parse_test_vec(EXAMPLE1, input_transform).unwrap();
     assert_eq!(part1(&input), 7);
     assert_eq!(part2(&input), 33);
 }
@@ -1,10 +1,13 @@
 use std::{collections::HashMap, error::Error};
 
-use aoc::input::parse_input_vec;
+use aoc::{
+    input::{TokenError, try_parse_input_vec},
+    parse::{parse_all, sep_by1, tag, terminated, word, ws},
+};
 
 fn main() -> Result<(), Box<dyn Error>> {
     // Get input
-    let input = parse_input_vec(11, input_transform)?;
+    let input = try_parse_input_vec(11, input_transform)?;
     let map = create_map(&input);
 
     // Run parts
@@ -119,14 +122,13 @@ struct InputEnt {
     to: Vec<String>,
 }
 
-fn input_transform(line: &str) -> InputEnt {
-    let mut iter = line.split_ascii_whitespace();
+fn input_transform(line: &str) -> Result<InputEnt, TokenError> {
+    let (from, _, to) = parse_all((terminated(word, tag(":")), ws, sep_by1(word, ws)), line)?;
 
-    let from = iter.next().unwrap().trim_end_matches(':').to_string();
-
-    let to = iter.map(|s| s.to_string()).collect::<Vec<_>>();
-
-    InputEnt { from, to }
+    Ok(InputEnt {
+        from: from.to_string(),
+        to: to.into_iter().map(String::from).collect(),
+    })
 }
 
 fn create_map(input: &[InputEnt]) -> HashMap<String, Vec<String>> {
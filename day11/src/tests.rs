@@ -1,4 +1,4 @@
-use aoc::input::parse_test_vec;
+use aoc::input::try_parse_test_vec;
 
 use super::*;
 
@@ -33,11 +33,11 @@ hhh: out
 
 #[test]
 fn test1() {
-    let input = parse_test_vec(EXAMPLE1, input_transform).unwrap();
+    let input = try_parse_test_vec(EXAMPLE1, input_transform).unwrap();
     let map = create_map(&input);
     assert_eq!(part1(&map), 5);
 
-    let input = parse_test_vec(EXAMPLE2, input_transform).unwrap();
+    let input = try_parse_test_vec(EXAMPLE2, input_transform).unwrap();
     let map = create_map(&input);
     assert_eq!(part2(&map), 2);
 }
@@ -1,10 +1,13 @@
 use std::{collections::HashSet, error::Error};
 
-use aoc::input::parse_input;
+use aoc::{
+    input::{Section, TokenError, try_parse_input_sections},
+    parse::{int, many1, map, one_of, parse_all, sep_by1, tag, terminated, ws},
+};
 
 fn main() -> Result<(), Box<dyn Error>> {
     // Get input
-    let (shapes, boards) = parse_input(12, input_transform)?;
+    let (shapes, boards) = collect_blocks(try_parse_input_sections(12, section_transform)?);
 
     // Run parts
     println!("Part 1: {}", part1(&shapes, &boards, false));
@@ -143,42 +146,56 @@ struct Board {
     shapes: Vec<usize>,
 }
 
-fn input_transform(input: &str) -> (Vec<Shape>, Vec<Board>) {
+enum Block {
+    Shape(Shape),
+    Boards(Vec<Board>),
+}
+
+fn section_transform(section: Section) -> Result<Block, TokenError> {
+    let mut lines = section.lines().peekable();
+
+    if let Some(header) = lines.next_if(|line| line.ends_with(':')) {
+        // Shape
+        parse_all(terminated(int::<usize>(), tag(":")), header)?;
+
+        let chars = lines
+            .map(|line| parse_all(many1(one_of("#.")), line))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Block::Shape(build_shape(chars)))
+    } else {
+        // Boards
+        let board = (
+            int::<u8>(),
+            tag("x"),
+            int::<u8>(),
+            tag(":"),
+            ws,
+            sep_by1(int::<usize>(), ws),
+        );
+
+        let mut board = map(board, |(width, _, height, _, _, shapes)| Board {
+            width,
+            height,
+            shapes,
+        });
+
+        Ok(Block::Boards(
+            lines
+                .map(|line| parse_all(&mut board, line))
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+}
+
+fn collect_blocks(blocks: Vec<Block>) -> (Vec<Shape>, Vec<Board>) {
     let mut shapes = Vec::new();
     let mut boards = Vec::new();
 
-    for section in input.split("\n\n") {
-        if section.lines().next().unwrap().ends_with(':') {
-            // Shape
-            let chars: Vec<Vec<char>> = section
-                .lines()
-                .skip(1)
-                .map(|line| line.chars().collect())
-                .collect();
-
-            shapes.push(build_shape(chars));
-        } else {
-            // Boards
-            boards = section
-                .lines()
-                .map(|line| {
-                    let (dim, shapes) = line.split_once(':').unwrap();
-
-                    let (w, h) = dim.split_once('x').unwrap();
-
-                    let shapes = shapes
-                        .trim()
-                        .split(' ')
-                        .map(|shape| shape.parse::<usize>().unwrap())
-                        .collect::<Vec<_>>();
-
-                    Board {
-                        width: w.parse::<u8>().unwrap(),
-                        height: h.parse::<u8>().unwrap(),
-                        shapes,
-                    }
-                })
-                .collect();
+    for block in blocks {
+        match block {
+            Block::Shape(shape) => shapes.push(shape),
+            Block::Boards(b) => boards = b,
         }
     }
 
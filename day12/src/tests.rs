@@ -1,3 +1,5 @@
+use aoc::input::try_parse_test_sections;
+
 use super::*;
 
 const EXAMPLE1: &str = "\
@@ -38,6 +40,7 @@ const EXAMPLE1: &str = "\
 
 #[test]
 fn test1() {
-    let (shapes, boards) = input_transform(EXAMPLE1);
+    let (shapes, boards) =
+        collect_blocks(try_parse_test_sections(EXAMPLE1, section_transform).unwrap());
     assert_eq!(part1(&shapes, &boards, true), 2);
 }